@@ -0,0 +1,118 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Runtime API definition for the multitoken pallet.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::{Codec, Decode, Encode};
+use sp_runtime::{traits::ConstU32, BoundedVec, RuntimeDebug};
+use sp_weights::Weight;
+
+/// Maximum number of operators that can be queried at once by `are_approved_for_all`.
+pub const MAX_QUERIED_OPERATORS: u32 = 64;
+
+/// Maximum number of collections that can be queried at once by `all_exist`.
+pub const MAX_QUERIED_COLLECTIONS: u32 = 64;
+
+/// Rule that grants or denies an operator moving tokens on behalf of their owner.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub enum AuthorizationResult<Amount> {
+    /// The operator is the owner of the tokens.
+    GrantedAsOwner,
+    /// The operator is approved for all of the owner's tokens, without a cap.
+    GrantedByGlobal,
+    /// The operator holds a capped approval that still covers the amount.
+    GrantedByCap { remaining: Amount },
+    /// The owner cannot move its tokens of the collection because of a pause or a freeze.
+    DeniedFrozen,
+    /// The operator is not approved by the owner.
+    DeniedNotApproved,
+    /// The approval of the operator has expired.
+    DeniedExpired,
+    /// The capped approval, the allowance or the one-shot approval of the operator does not
+    /// cover the amount.
+    DeniedInsufficientAllowance { remaining: Amount },
+    /// The operator is approved for the tokens of the collection only.
+    GrantedByCollection,
+    /// The operator holds an allowance for the collection that still covers the amount.
+    GrantedByAllowance { remaining: Amount },
+    /// The operator holds a one-shot approval for a single transfer of up to `amount`.
+    GrantedOnce { amount: Amount },
+    /// The operator is a sub-operator of the owner whose cap, bounded by the approval cap of the
+    /// delegable operator that started the delegation, still covers the amount.
+    GrantedBySubOperator { remaining: Amount },
+}
+
+sp_api::decl_runtime_apis! {
+    /// Read-only queries over the multitoken pallet state.
+    pub trait MultitokenApi<AccountId, CollectionId, Amount>
+    where
+        AccountId: Codec,
+        CollectionId: Codec,
+        Amount: Codec,
+    {
+        /// Returns the amount of tokens of collection `id` owned by `account`.
+        fn balance_of(account: AccountId, id: CollectionId) -> Amount;
+
+        /// Returns true only if collection `id` exists and is owned by `who`.
+        fn is_collection_owner(id: CollectionId, who: AccountId) -> bool;
+
+        /// Returns, in input order, whether each of `operators` is approved to transfer
+        /// `account`'s tokens.
+        fn are_approved_for_all(
+            account: AccountId,
+            operators: BoundedVec<AccountId, ConstU32<MAX_QUERIED_OPERATORS>>,
+        ) -> Vec<bool>;
+
+        /// Returns the operators currently approved to transfer all of `account`'s tokens.
+        fn operators_of(account: AccountId) -> Vec<AccountId>;
+
+        /// Returns the id of the most recently created collection, if any.
+        fn last_collection_id() -> Option<CollectionId>;
+
+        /// Returns true only if collection `id` exists and is fungible.
+        fn is_fungible_collection(id: CollectionId) -> bool;
+
+        /// Returns true if any account holds tokens of collection `id`.
+        fn has_holders(id: CollectionId) -> bool;
+
+        /// Returns true if every collection in `ids` exists.
+        fn all_exist(ids: BoundedVec<CollectionId, ConstU32<MAX_QUERIED_COLLECTIONS>>) -> bool;
+
+        /// Returns true if `account` cannot currently move its tokens of collection `id` because
+        /// of a pause or a freeze.
+        fn is_frozen(id: CollectionId, account: AccountId) -> bool;
+
+        /// Returns the amount of tokens of collection `id` that `account` can currently move.
+        fn transferable_balance(id: CollectionId, account: AccountId) -> Amount;
+
+        /// Returns the `n` largest holders of collection `id` sorted by descending balance.
+        /// `n` is capped by the pallet, so large values do not mean more work.
+        fn top_holders(id: CollectionId, n: u32) -> Vec<(AccountId, Amount)>;
+
+        /// Returns the balances of `account` in the collections owned by `creator`.
+        fn balances_of_account_by_creator(
+            account: AccountId,
+            creator: AccountId,
+        ) -> Vec<(CollectionId, Amount)>;
+
+        /// Returns the weight of a `safe_batch_transfer_from` call moving `n` ids.
+        fn transfer_batch_weight(n: u32) -> Weight;
+
+        /// Returns how many collections can be created by `create` within `available` weight.
+        fn max_creates_for_weight(available: Weight) -> u32;
+
+        /// Returns the circulating, reserved and burned amounts of collection `id`.
+        fn supply_breakdown(id: CollectionId) -> (Amount, Amount, Amount);
+
+        /// Explains whether `operator` can move `amount` of `owner`'s tokens of collection `id`,
+        /// and which rule decides it.
+        fn explain_authorization(
+            owner: AccountId,
+            operator: AccountId,
+            id: CollectionId,
+            amount: Amount,
+        ) -> AuthorizationResult<Amount>;
+    }
+}
@@ -1 +1,1081 @@
+//! Benchmarking setup for pallet-multitoken
 
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Multitoken;
+use alloc::vec;
+use alloc::vec::Vec;
+use frame_benchmarking::v2::*;
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Hash, Zero};
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+    frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+/// Creates `n` fungible collections owned by `owner`, ignoring the per-block creation limit.
+fn create_collections<T: Config>(owner: &T::AccountId, n: u32) -> Vec<T::CollectionId> {
+    (0..n)
+        .map(|_| {
+            CreatesInBlock::<T>::kill();
+            Pallet::<T>::create_collection(owner.clone(), true).unwrap()
+        })
+        .collect()
+}
+
+/// Mints `amount` of every collection in `ids` to `to`. `owner` must own all of them.
+fn mint_to<T: Config>(
+    owner: &T::AccountId,
+    to: &T::AccountId,
+    ids: &[T::CollectionId],
+    amount: T::Amount,
+) {
+    for id in ids {
+        Pallet::<T>::mint(RawOrigin::Signed(owner.clone()).into(), to.clone(), *id, amount)
+            .unwrap();
+    }
+}
+
+/// Enforces the recipient allowlist of every collection in `ids`, allowing only `receiver`.
+/// Transfers to `receiver` then go through the most expensive recipient checks.
+fn restrict_recipients<T: Config>(
+    owner: &T::AccountId,
+    ids: &[T::CollectionId],
+    receiver: &T::AccountId,
+) {
+    for id in ids {
+        let origin: T::RuntimeOrigin = RawOrigin::Signed(owner.clone()).into();
+        Pallet::<T>::allow_recipient(origin.clone(), *id, receiver.clone(), true).unwrap();
+        Pallet::<T>::set_recipient_allowlist_enforced(origin, *id, true).unwrap();
+    }
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn set_approval_for_all() {
+        let caller: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account("operator", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), operator.clone(), true, None);
+
+        assert!(Pallet::<T>::is_approved_for_all(&caller, &operator));
+    }
+
+    #[benchmark]
+    fn safe_transfer_from() {
+        let owner: T::AccountId = account("owner", 0, 0);
+        let operator: T::AccountId = whitelisted_caller();
+        let receiver: T::AccountId = account("receiver", 0, 0);
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, 1);
+        mint_to::<T>(&owner, &owner, &ids, amount);
+        restrict_recipients::<T>(&owner, &ids, &receiver);
+        Pallet::<T>::set_capped_approval(
+            RawOrigin::Signed(owner.clone()).into(),
+            operator.clone(),
+            amount,
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(operator), owner.clone(), receiver.clone(), ids[0], amount);
+
+        assert_eq!(Pallet::<T>::balance_of(&receiver, &ids[0]), amount);
+        assert!(Balances::<T>::get(ids[0], &owner).is_none());
+    }
+
+    #[benchmark]
+    fn safe_batch_transfer_from(n: Linear<1, { T::MaxBatchSize::get() }>) {
+        let owner: T::AccountId = whitelisted_caller();
+        let receiver: T::AccountId = account("receiver", 0, 0);
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, n);
+        mint_to::<T>(&owner, &owner, &ids, amount);
+        restrict_recipients::<T>(&owner, &ids, &receiver);
+        let amounts = vec![amount; n as usize];
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(owner.clone()),
+            owner.clone(),
+            receiver.clone(),
+            ids.clone(),
+            amounts,
+        );
+
+        for id in ids.iter() {
+            assert_eq!(Pallet::<T>::balance_of(&receiver, id), amount);
+            assert!(Balances::<T>::get(id, &owner).is_none());
+        }
+    }
+
+    #[benchmark]
+    fn mint() {
+        let owner: T::AccountId = whitelisted_caller();
+        let receiver: T::AccountId = account("receiver", 0, 0);
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), receiver.clone(), ids[0], amount);
+
+        assert_eq!(Pallet::<T>::balance_of(&receiver, &ids[0]), amount);
+    }
+
+    #[benchmark]
+    fn mint_batch(n: Linear<1, { T::MaxBatchSize::get() }>) {
+        let owner: T::AccountId = whitelisted_caller();
+        let receiver: T::AccountId = account("receiver", 0, 0);
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, n);
+        let amounts = vec![amount; n as usize];
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), receiver.clone(), ids.clone(), amounts);
+
+        for id in ids.iter() {
+            assert_eq!(Pallet::<T>::balance_of(&receiver, id), amount);
+        }
+    }
+
+    #[benchmark]
+    fn burn() {
+        let owner: T::AccountId = whitelisted_caller();
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, 1);
+        mint_to::<T>(&owner, &owner, &ids, amount);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), ids[0], amount);
+
+        assert!(Pallet::<T>::balance_of(&owner, &ids[0]).is_zero());
+    }
+
+    #[benchmark]
+    fn burn_batch(n: Linear<1, { T::MaxBatchSize::get() }>) {
+        let owner: T::AccountId = whitelisted_caller();
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, n);
+        mint_to::<T>(&owner, &owner, &ids, amount);
+        let amounts = vec![amount; n as usize];
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), ids.clone(), amounts);
+
+        for id in ids.iter() {
+            assert!(Pallet::<T>::balance_of(&owner, id).is_zero());
+        }
+    }
+
+    #[benchmark]
+    fn create() {
+        let owner: T::AccountId = whitelisted_caller();
+        let id = NextCollectionId::<T>::get();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), true);
+
+        assert!(Pallet::<T>::is_collection_owner(&id, &owner));
+    }
+
+    #[benchmark]
+    fn merge_collections(n: Linear<1, 100>) {
+        let owner: T::AccountId = whitelisted_caller();
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, 2);
+        for i in 0..n {
+            let holder: T::AccountId = account("holder", i, 0);
+            mint_to::<T>(&owner, &holder, &ids, amount);
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], ids[1], n);
+
+        assert_eq!(CollectionDestroying::<T>::get(ids[0]), Some(DestroyStage::Accounts));
+    }
+
+    #[benchmark]
+    fn emit_metadata_update() {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0]);
+
+        assert_last_event::<T>(Event::<T>::MetadataUpdated { id: ids[0] }.into());
+    }
+
+    #[benchmark]
+    fn allow_claim() {
+        let owner: T::AccountId = whitelisted_caller();
+        let player: T::AccountId = account("player", 0, 0);
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], player.clone(), amount);
+
+        assert_eq!(Claimable::<T>::get(ids[0], &player), amount);
+    }
+
+    #[benchmark]
+    fn claim_and_approve() {
+        let owner: T::AccountId = account("owner", 0, 0);
+        let player: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account("operator", 0, 0);
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, 1);
+        Claimable::<T>::insert(ids[0], &player, amount);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(player.clone()), ids[0], operator.clone());
+
+        assert_eq!(Pallet::<T>::balance_of(&player, &ids[0]), amount);
+        assert!(Pallet::<T>::is_approved_for_all(&player, &operator));
+    }
+
+    #[benchmark]
+    fn mint_and_distribute(n: Linear<1, { T::MaxBatchSize::get() }>) {
+        let owner: T::AccountId = whitelisted_caller();
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, 1);
+        let recipients: Vec<_> = (0..n)
+            .map(|i| (account::<T::AccountId>("recipient", i, 0), amount))
+            .collect();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], recipients.clone());
+
+        for (recipient, amount) in recipients.iter() {
+            assert_eq!(Pallet::<T>::balance_of(recipient, &ids[0]), *amount);
+        }
+    }
+
+    #[benchmark]
+    fn allow_recipient() {
+        let owner: T::AccountId = whitelisted_caller();
+        let recipient: T::AccountId = account("recipient", 0, 0);
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], recipient.clone(), true);
+
+        assert!(RecipientAllowlist::<T>::get(ids[0], &recipient));
+    }
+
+    #[benchmark]
+    fn set_recipient_allowlist_enforced() {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], true);
+
+        assert!(RecipientAllowlistEnforced::<T>::get(ids[0]));
+    }
+
+    #[benchmark]
+    fn claim_mint() {
+        let claimer: T::AccountId = whitelisted_caller();
+        let amount: T::Amount = 100u32.into();
+        let id = NextCollectionId::<T>::get();
+        let payload = Pallet::<T>::claim_payload(&id, &claimer, amount, 0);
+        let (owner, signature) = T::BenchmarkHelper::sign(&payload);
+        create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(claimer.clone()), id, amount, signature);
+
+        assert_eq!(Pallet::<T>::balance_of(&claimer, &id), amount);
+    }
+
+    #[benchmark]
+    fn transfer() {
+        let owner: T::AccountId = whitelisted_caller();
+        let receiver: T::AccountId = account("receiver", 0, 0);
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, 1);
+        mint_to::<T>(&owner, &owner, &ids, amount);
+        restrict_recipients::<T>(&owner, &ids, &receiver);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), receiver.clone(), ids[0], amount);
+
+        assert_eq!(Pallet::<T>::balance_of(&receiver, &ids[0]), amount);
+        assert!(Balances::<T>::get(ids[0], &owner).is_none());
+    }
+
+    #[benchmark]
+    fn set_collection_hook() {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], Some(1));
+
+        assert_eq!(CollectionHook::<T>::get(ids[0]), Some(1));
+    }
+
+    #[benchmark]
+    fn burn_batch_from(n: Linear<1, { T::MaxBatchSize::get() }>) {
+        let owner: T::AccountId = account("owner", 0, 0);
+        let operator: T::AccountId = whitelisted_caller();
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, n);
+        mint_to::<T>(&owner, &owner, &ids, amount);
+        Pallet::<T>::set_approval_for_all(
+            RawOrigin::Signed(owner.clone()).into(),
+            operator.clone(),
+            true,
+            None,
+        )
+        .unwrap();
+        let amounts = vec![amount; n as usize];
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(operator), owner.clone(), ids.clone(), amounts);
+
+        for id in ids.iter() {
+            assert!(Pallet::<T>::balance_of(&owner, id).is_zero());
+        }
+    }
+
+    #[benchmark]
+    fn force_set_balance() {
+        let owner: T::AccountId = account("owner", 0, 0);
+        let holder: T::AccountId = account("holder", 0, 0);
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, ids[0], holder.clone(), amount);
+
+        assert_eq!(Pallet::<T>::balance_of(&holder, &ids[0]), amount);
+    }
+
+    #[benchmark]
+    fn destroy_collection() {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0]);
+
+        assert_eq!(CollectionDestroying::<T>::get(ids[0]), Some(DestroyStage::Accounts));
+    }
+
+    #[benchmark]
+    fn set_paused() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, true);
+
+        assert!(Paused::<T>::get());
+    }
+
+    #[benchmark]
+    fn freeze_collection() {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], true);
+
+        assert!(CollectionsV2::<T>::get(ids[0]).unwrap().frozen);
+    }
+
+    #[benchmark]
+    fn freeze_account() {
+        let owner: T::AccountId = whitelisted_caller();
+        let holder: T::AccountId = account("holder", 0, 0);
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], holder.clone(), true);
+
+        assert!(FrozenAccounts::<T>::get(ids[0], &holder));
+    }
+
+    #[benchmark]
+    fn set_uri() {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+        let uri = vec![0u8; T::MaxUriLength::get() as usize];
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], uri.clone());
+
+        assert_eq!(CollectionUri::<T>::get(ids[0]).unwrap().into_inner(), uri);
+    }
+
+    #[benchmark]
+    fn mint_batch_with_uris(n: Linear<1, { T::MaxBatchSize::get() }>) {
+        let owner: T::AccountId = whitelisted_caller();
+        let receiver: T::AccountId = account("receiver", 0, 0);
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, n);
+        let amounts = vec![amount; n as usize];
+        let uris = vec![vec![0u8; T::MaxUriLength::get() as usize]; n as usize];
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), receiver.clone(), ids.clone(), amounts, uris);
+
+        for id in ids.iter() {
+            assert_eq!(Pallet::<T>::balance_of(&receiver, id), amount);
+            assert!(CollectionUri::<T>::contains_key(id));
+        }
+    }
+
+    #[benchmark]
+    fn set_metadata() {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+        let value = vec![b'a'; T::StringLimit::get() as usize];
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], value.clone(), value);
+
+        assert!(Metadata::<T>::contains_key(ids[0]));
+    }
+
+    #[benchmark]
+    fn set_scale() {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], 18);
+
+        assert_eq!(CollectionScale::<T>::get(ids[0]), 18);
+    }
+
+    #[benchmark]
+    fn bridge_mint() -> Result<(), BenchmarkError> {
+        let owner: T::AccountId = account("owner", 0, 0);
+        let receiver: T::AccountId = account("receiver", 0, 0);
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, 1);
+        let message_hash = T::Hashing::hash(b"message");
+        let origin =
+            T::BridgeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, receiver.clone(), ids[0], amount, message_hash);
+
+        assert_eq!(Pallet::<T>::balance_of(&receiver, &ids[0]), amount);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_capped_approval() {
+        let caller: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account("operator", 0, 0);
+        let cap: T::Amount = 100u32.into();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), operator.clone(), cap);
+
+        assert_eq!(ApprovalCaps::<T>::get(&caller, &operator), Some(cap));
+    }
+
+    #[benchmark]
+    fn mint_idempotent() {
+        let owner: T::AccountId = whitelisted_caller();
+        let receiver: T::AccountId = account("receiver", 0, 0);
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), receiver.clone(), ids[0], amount, 0);
+
+        assert_eq!(Pallet::<T>::balance_of(&receiver, &ids[0]), amount);
+        assert!(ClientNonces::<T>::contains_key((owner, 0)));
+    }
+
+    #[benchmark]
+    fn ensure_collection_and_mint() -> Result<(), BenchmarkError> {
+        if !T::CustomCollectionIds::get() {
+            return Err(BenchmarkError::Weightless);
+        }
+        let owner: T::AccountId = whitelisted_caller();
+        let receiver: T::AccountId = account("receiver", 0, 0);
+        let amount: T::Amount = 100u32.into();
+        let id = NextCollectionId::<T>::get();
+        CreatesInBlock::<T>::kill();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), id, receiver.clone(), amount);
+
+        assert!(Pallet::<T>::is_collection_owner(&id, &owner));
+        assert_eq!(Pallet::<T>::balance_of(&receiver, &id), amount);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn distribute(n: Linear<1, { T::MaxBatchSize::get() }>) {
+        let owner: T::AccountId = whitelisted_caller();
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, 1);
+        mint_to::<T>(&owner, &owner, &ids, (100 * n).into());
+        let recipients: Vec<(T::AccountId, T::Amount)> = (0..n)
+            .map(|i| (account("receiver", i, 0), amount))
+            .collect();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), ids[0], recipients);
+
+        assert!(Pallet::<T>::balance_of(&owner, &ids[0]).is_zero());
+    }
+
+    #[benchmark]
+    fn force_clear_balances(n: Linear<1, 1_000>) {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+        for i in 0..n {
+            let holder: T::AccountId = account("holder", i, 0);
+            mint_to::<T>(&owner, &holder, &ids, 100u32.into());
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, ids[0], n);
+
+        assert!(Pallet::<T>::total_supply(ids[0]).is_zero());
+        assert_last_event::<T>(Event::CollectionCleared { id: ids[0] }.into());
+    }
+
+    #[benchmark]
+    fn set_transfer_tax() {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+        let tax = TransferTax {
+            owner_bps: 100,
+            treasury_bps: 50,
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], tax);
+
+        assert_eq!(Pallet::<T>::transfer_tax(ids[0]), Some(tax));
+    }
+
+    #[benchmark]
+    fn create_deterministic() -> Result<(), BenchmarkError> {
+        if !T::CustomCollectionIds::get() {
+            return Err(BenchmarkError::Weightless);
+        }
+        let owner: T::AccountId = whitelisted_caller();
+        let salt = [7u8; 32];
+        let id = Pallet::<T>::deterministic_collection_id(&owner, &salt)?;
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), salt);
+
+        assert_eq!(Pallet::<T>::collection_owner(&id), Some(owner));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_royalty() {
+        let owner: T::AccountId = whitelisted_caller();
+        let recipient: T::AccountId = account("recipient", 0, 0);
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], recipient.clone(), 500);
+
+        assert_eq!(
+            Pallet::<T>::royalty(ids[0]),
+            Some(RoyaltyInfo {
+                recipient,
+                bps: 500
+            })
+        );
+    }
+
+    #[benchmark]
+    fn set_royalty_recipient() {
+        let owner: T::AccountId = whitelisted_caller();
+        let recipient: T::AccountId = account("recipient", 0, 0);
+        let ids = create_collections::<T>(&owner, 1);
+        Pallet::<T>::set_royalty(
+            RawOrigin::Signed(owner.clone()).into(),
+            ids[0],
+            owner.clone(),
+            500,
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], recipient.clone());
+
+        assert_eq!(
+            Pallet::<T>::royalty(ids[0]),
+            Some(RoyaltyInfo {
+                recipient,
+                bps: 500
+            })
+        );
+    }
+
+    #[benchmark]
+    fn execute_sale() {
+        let seller: T::AccountId = account("seller", 0, 0);
+        let buyer: T::AccountId = account("buyer", 0, 0);
+        let marketplace: T::AccountId = whitelisted_caller();
+        let recipient: T::AccountId = account("recipient", 0, 0);
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&seller, 2);
+        mint_to::<T>(&seller, &seller, &ids[..1], amount);
+        mint_to::<T>(&seller, &buyer, &ids[1..], amount);
+        Pallet::<T>::set_royalty(
+            RawOrigin::Signed(seller.clone()).into(),
+            ids[0],
+            recipient.clone(),
+            500,
+        )
+        .unwrap();
+        for account in [&seller, &buyer] {
+            Pallet::<T>::set_approval_for_all(
+                RawOrigin::Signed(account.clone()).into(),
+                marketplace.clone(),
+                true,
+                None,
+            )
+            .unwrap();
+        }
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(marketplace),
+            seller.clone(),
+            buyer.clone(),
+            ids[0],
+            amount,
+            ids[1],
+            amount,
+        );
+
+        assert_eq!(Pallet::<T>::balance_of(&buyer, &ids[0]), amount);
+        assert_eq!(Pallet::<T>::balance_of(&recipient, &ids[1]), 5u32.into());
+    }
+
+    #[benchmark]
+    fn set_max_supply() {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+        let max_supply: Option<T::Amount> = Some(1_000u32.into());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], max_supply);
+
+        assert_eq!(Pallet::<T>::collection_details(ids[0]).unwrap().max_supply, max_supply);
+    }
+
+    #[benchmark]
+    fn set_inflation_schedule() {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+        let schedule: (T::Amount, T::AccountId) = (10u32.into(), owner.clone());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], Some(schedule.clone()));
+
+        assert_eq!(Pallet::<T>::inflation_schedule(ids[0]), Some(schedule));
+    }
+
+    #[benchmark]
+    fn process_inflation(n: Linear<0, { T::MaxInflationsPerBlock::get() }>) {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, n);
+        for id in ids.iter() {
+            InflationSchedule::<T>::insert(id, (T::Amount::from(10u32), owner.clone()));
+        }
+
+        #[block]
+        {
+            Pallet::<T>::process_inflation();
+        }
+
+        for id in ids.iter() {
+            assert_eq!(Pallet::<T>::balance_of(&owner, id), 10u32.into());
+        }
+    }
+
+    #[benchmark]
+    fn safe_transfer_from_with_memo() {
+        let operator: T::AccountId = whitelisted_caller();
+        let owner: T::AccountId = account("owner", 0, 0);
+        let receiver: T::AccountId = account("receiver", 0, 0);
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, 1);
+        mint_to::<T>(&owner, &owner, &ids, amount);
+        restrict_recipients::<T>(&owner, &ids, &receiver);
+        Pallet::<T>::set_capped_approval(
+            RawOrigin::Signed(owner.clone()).into(),
+            operator.clone(),
+            amount,
+        )
+        .unwrap();
+        let memo = BoundedVec::truncate_from(vec![0u8; T::MaxMemoLength::get() as usize]);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(operator), owner, receiver.clone(), ids[0], amount, memo.clone());
+
+        assert_eq!(Pallet::<T>::balance_of(&receiver, &ids[0]), amount);
+        assert_last_event::<T>(Event::TransferMemo { id: ids[0], memo }.into());
+    }
+
+    #[benchmark]
+    fn pause_collection() {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0]);
+
+        assert!(CollectionPaused::<T>::get(ids[0]));
+    }
+
+    #[benchmark]
+    fn unpause_collection() {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+        CollectionPaused::<T>::insert(ids[0], true);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0]);
+
+        assert!(!CollectionPaused::<T>::get(ids[0]));
+    }
+
+    #[benchmark]
+    fn set_orphan_fallback() {
+        let fallback: T::AccountId = account("fallback", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, Some(fallback.clone()));
+
+        assert_eq!(OrphanFallback::<T>::get(), Some(fallback));
+    }
+
+    #[benchmark]
+    fn set_approval_for_collection() {
+        let owner: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account("operator", 0, 0);
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), operator.clone(), ids[0], true, None);
+
+        assert!(CollectionApprovals::<T>::contains_key(&owner, (ids[0], &operator)));
+    }
+
+    #[benchmark]
+    fn approve() {
+        let owner: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account("operator", 0, 0);
+        let ids = create_collections::<T>(&owner, 1);
+        let amount: T::Amount = 100u32.into();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), operator.clone(), ids[0], amount, None);
+
+        assert_eq!(Allowances::<T>::get(&owner, (ids[0], &operator)), Some((amount, None)));
+    }
+
+    #[benchmark]
+    fn clear_all_approvals(n: Linear<0, { T::MaxApprovalsCleared::get() }>) {
+        let owner: T::AccountId = whitelisted_caller();
+        for i in 0..n {
+            Pallet::<T>::set_capped_approval(
+                RawOrigin::Signed(owner.clone()).into(),
+                account("operator", i, 0),
+                100u32.into(),
+            )
+            .unwrap();
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()));
+
+        assert!(OperatorApprovals::<T>::iter_prefix(&owner).next().is_none());
+    }
+
+    #[benchmark]
+    fn increase_allowance() {
+        let owner: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account("operator", 0, 0);
+        let ids = create_collections::<T>(&owner, 1);
+        let amount: T::Amount = 100u32.into();
+        Allowances::<T>::insert(&owner, (ids[0], &operator), (amount, None));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), operator.clone(), ids[0], amount);
+
+        assert_eq!(
+            Allowances::<T>::get(&owner, (ids[0], &operator)),
+            Some((amount + amount, None))
+        );
+    }
+
+    #[benchmark]
+    fn decrease_allowance() {
+        let owner: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account("operator", 0, 0);
+        let ids = create_collections::<T>(&owner, 1);
+        let amount: T::Amount = 100u32.into();
+        Allowances::<T>::insert(&owner, (ids[0], &operator), (amount + amount, None));
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), operator.clone(), ids[0], amount);
+
+        assert_eq!(Allowances::<T>::get(&owner, (ids[0], &operator)), Some((amount, None)));
+    }
+
+    #[benchmark]
+    fn permit_approval() {
+        let caller: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account("operator", 0, 0);
+        let deadline = frame_system::Pallet::<T>::block_number();
+        let (owner, _) = T::BenchmarkHelper::sign(&[]);
+        let payload = Pallet::<T>::permit_payload(&owner, &operator, true, 0, deadline);
+        let (_, signature) = T::BenchmarkHelper::sign(&payload);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), owner.clone(), operator.clone(), true, deadline, signature);
+
+        assert!(Pallet::<T>::is_approved_for_all(&owner, &operator));
+    }
+
+    #[benchmark]
+    fn set_operator_delegable() {
+        let owner: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account("operator", 0, 0);
+        Pallet::<T>::set_approval_for_all(
+            RawOrigin::Signed(owner.clone()).into(),
+            operator.clone(),
+            true,
+            None,
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), operator.clone(), true);
+
+        assert!(DelegableOperators::<T>::contains_key(&owner, &operator));
+    }
+
+    #[benchmark]
+    fn set_sub_operator() {
+        let owner: T::AccountId = account("owner", 0, 0);
+        let operator: T::AccountId = account("operator", 0, 0);
+        let cap: T::Amount = 100u32.into();
+        Pallet::<T>::set_approval_for_all(
+            RawOrigin::Signed(owner.clone()).into(),
+            operator.clone(),
+            true,
+            None,
+        )
+        .unwrap();
+        Pallet::<T>::set_operator_delegable(
+            RawOrigin::Signed(owner.clone()).into(),
+            operator.clone(),
+            true,
+        )
+        .unwrap();
+        // The deepest delegation walks the longest chain of delegators.
+        let mut delegator = operator;
+        for i in 1..T::MaxDelegationDepth::get() {
+            let sub_operator: T::AccountId = account("sub_operator", i, 0);
+            Pallet::<T>::set_sub_operator(
+                RawOrigin::Signed(delegator).into(),
+                owner.clone(),
+                sub_operator.clone(),
+                Some(cap),
+            )
+            .unwrap();
+            delegator = sub_operator;
+        }
+        let sub_operator: T::AccountId = whitelisted_caller();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(delegator), owner.clone(), sub_operator.clone(), Some(cap));
+
+        assert!(SubOperators::<T>::contains_key(&owner, &sub_operator));
+    }
+
+    #[benchmark]
+    fn approve_transfer_once() {
+        let owner: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account("operator", 0, 0);
+        let ids = create_collections::<T>(&owner, 1);
+        let amount: T::Amount = 100u32.into();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), operator.clone(), ids[0], amount);
+
+        assert_eq!(OneShotApprovals::<T>::get(&owner, (ids[0], &operator)), Some(amount));
+    }
+
+    #[benchmark]
+    fn burn_from() {
+        let owner: T::AccountId = account("owner", 0, 0);
+        let operator: T::AccountId = whitelisted_caller();
+        let amount: T::Amount = 100u32.into();
+        let ids = create_collections::<T>(&owner, 1);
+        mint_to::<T>(&owner, &owner, &ids, amount);
+        Pallet::<T>::set_capped_approval(
+            RawOrigin::Signed(owner.clone()).into(),
+            operator.clone(),
+            amount,
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(operator), owner.clone(), ids[0], amount);
+
+        assert!(Pallet::<T>::balance_of(&owner, &ids[0]).is_zero());
+    }
+
+    #[benchmark]
+    fn set_approvals(n: Linear<1, { T::MaxBatchSize::get() }>) {
+        let owner: T::AccountId = whitelisted_caller();
+        let approvals: Vec<_> = (0..n).map(|i| (account("operator", i, 0), true)).collect();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), approvals);
+
+        assert_eq!(OperatorApprovals::<T>::iter_prefix(&owner).count(), n as usize);
+    }
+
+    #[benchmark]
+    fn transfer_collection_ownership() {
+        let owner: T::AccountId = whitelisted_caller();
+        let new_owner: T::AccountId = account("new_owner", 0, 0);
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], new_owner.clone());
+
+        assert_eq!(PendingCollectionOwner::<T>::get(ids[0]), Some(new_owner));
+    }
+
+    #[benchmark]
+    fn accept_collection_ownership() -> Result<(), BenchmarkError> {
+        let owner: T::AccountId = account("owner", 0, 0);
+        let new_owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+        let issuer: T::AccountId = account("issuer", 0, 0);
+        Pallet::<T>::set_team(
+            RawOrigin::Signed(owner.clone()).into(),
+            ids[0],
+            issuer.clone(),
+            issuer.clone(),
+            issuer,
+        )?;
+        Pallet::<T>::transfer_collection_ownership(
+            RawOrigin::Signed(owner).into(),
+            ids[0],
+            new_owner.clone(),
+        )?;
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(new_owner.clone()), ids[0]);
+
+        assert_eq!(Pallet::<T>::collection_owner(&ids[0]), Some(new_owner));
+        assert!(CollectionTeams::<T>::get(ids[0]).is_none());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_team() {
+        let owner: T::AccountId = whitelisted_caller();
+        let issuer: T::AccountId = account("issuer", 0, 0);
+        let admin: T::AccountId = account("admin", 0, 0);
+        let freezer: T::AccountId = account("freezer", 0, 0);
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], issuer.clone(), admin.clone(), freezer.clone());
+
+        assert_eq!(
+            CollectionTeams::<T>::get(ids[0]),
+            Some(CollectionTeam {
+                issuer,
+                admin,
+                freezer
+            })
+        );
+    }
+
+    #[benchmark]
+    fn start_destroy() {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0]);
+
+        assert_eq!(CollectionDestroying::<T>::get(ids[0]), Some(DestroyStage::Accounts));
+    }
+
+    #[benchmark]
+    fn destroy_accounts(a: Linear<0, 1_000>, p: Linear<0, 1_000>) -> Result<(), BenchmarkError> {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+        for i in 0..a {
+            let holder: T::AccountId = account("holder", i, 0);
+            mint_to::<T>(&owner, &holder, &ids, 100u32.into());
+        }
+        for i in 0..p {
+            let operator: T::AccountId = account("operator", i, 0);
+            Allowances::<T>::insert(&owner, (ids[0], &operator), (T::Amount::from(100u32), None));
+        }
+        Pallet::<T>::start_destroy(RawOrigin::Signed(owner.clone()).into(), ids[0])?;
+        // One more visit than there are approvals to find the end of each map.
+        let witness = DestroyWitness {
+            accounts: a,
+            approvals: p + 3,
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0], witness);
+
+        assert!(Balances::<T>::iter_prefix(ids[0]).next().is_none());
+        assert_eq!(CollectionDestroying::<T>::get(ids[0]), Some(DestroyStage::Done));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn finish_destroy() -> Result<(), BenchmarkError> {
+        let owner: T::AccountId = whitelisted_caller();
+        let ids = create_collections::<T>(&owner, 1);
+        Pallet::<T>::start_destroy(RawOrigin::Signed(owner.clone()).into(), ids[0])?;
+        CollectionDestroying::<T>::insert(ids[0], DestroyStage::Done);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), ids[0]);
+
+        assert!(CollectionsV2::<T>::get(ids[0]).is_none());
+        assert_last_event::<T>(Event::CollectionDestroyed { id: ids[0] }.into());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_mint_consent() {
+        let caller: T::AccountId = whitelisted_caller();
+        let minter: T::AccountId = account("minter", 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), minter.clone(), true);
+
+        assert!(MintConsent::<T>::contains_key(&caller, &minter));
+    }
+
+    impl_benchmark_test_suite!(Multitoken, crate::mock::new_test_ext(), crate::mock::Test);
+}
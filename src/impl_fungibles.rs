@@ -0,0 +1,132 @@
+//! Implementations of the `fungibles` traits of `frame_support`, so that other pallets can use
+//! the collections of this pallet as assets.
+
+use super::*;
+use alloc::vec;
+use frame_support::{
+    pallet_prelude::*,
+    traits::tokens::{
+        fungibles, DepositConsequence, Fortitude, Preservation, Provenance, WithdrawConsequence,
+    },
+};
+use sp_runtime::traits::{CheckedAdd, Zero};
+
+impl<T: Config> fungibles::Inspect<T::AccountId> for Pallet<T> {
+    type AssetId = T::CollectionId;
+    type Balance = T::Amount;
+
+    fn total_issuance(asset: Self::AssetId) -> Self::Balance {
+        Self::total_supply(asset)
+    }
+
+    fn minimum_balance(_asset: Self::AssetId) -> Self::Balance {
+        Zero::zero()
+    }
+
+    fn total_balance(asset: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+        Self::balance_of(who, &asset)
+    }
+
+    fn balance(asset: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+        Self::balance_of(who, &asset)
+    }
+
+    fn reducible_balance(
+        asset: Self::AssetId,
+        who: &T::AccountId,
+        _preservation: Preservation,
+        _force: Fortitude,
+    ) -> Self::Balance {
+        Self::transferable_balance(&asset, who)
+    }
+
+    fn can_deposit(
+        asset: Self::AssetId,
+        who: &T::AccountId,
+        amount: Self::Balance,
+        provenance: Provenance,
+    ) -> DepositConsequence {
+        let details = match CollectionsV2::<T>::get(asset) {
+            Some(details) if details.fungible => details,
+            _ => return DepositConsequence::UnknownAsset,
+        };
+        if provenance == Provenance::Minted {
+            match details.total_supply.checked_add(&amount) {
+                Some(supply) if !details.max_supply.is_some_and(|max| supply > max) => {}
+                _ => return DepositConsequence::Overflow,
+            }
+        }
+        if Self::balance_of(who, &asset).checked_add(&amount).is_none() {
+            return DepositConsequence::Overflow;
+        }
+        DepositConsequence::Success
+    }
+
+    fn can_withdraw(
+        asset: Self::AssetId,
+        who: &T::AccountId,
+        amount: Self::Balance,
+    ) -> WithdrawConsequence<Self::Balance> {
+        if !Self::is_fungible_collection(&asset) {
+            return WithdrawConsequence::UnknownAsset;
+        }
+        if Self::is_frozen(&asset, who) {
+            return WithdrawConsequence::Frozen;
+        }
+        if Self::balance_of(who, &asset) < amount {
+            return WithdrawConsequence::BalanceLow;
+        }
+        WithdrawConsequence::Success
+    }
+
+    /// Non-fungible collections are not exposed as assets.
+    fn asset_exists(asset: Self::AssetId) -> bool {
+        Self::is_fungible_collection(&asset)
+    }
+}
+
+/// Approvals through the standard interface map to the allowances of this pallet, so approvals
+/// for all and for whole collections are not reported by `allowance`, although `transfer_from`
+/// honours them like any other transfer made by an operator.
+impl<T: Config> fungibles::approvals::Inspect<T::AccountId> for Pallet<T> {
+    fn allowance(asset: Self::AssetId, owner: &T::AccountId, delegate: &T::AccountId) -> T::Amount {
+        let now = frame_system::Pallet::<T>::block_number();
+        match Allowances::<T>::get(owner, (asset, delegate)) {
+            Some((amount, deadline)) if is_unexpired(&deadline, &now) => amount,
+            _ => Zero::zero(),
+        }
+    }
+}
+
+impl<T: Config> fungibles::approvals::Mutate<T::AccountId> for Pallet<T> {
+    /// Sets the allowance of `delegate` to `amount` with no deadline, replacing the previous one.
+    fn approve(
+        asset: T::CollectionId,
+        owner: &T::AccountId,
+        delegate: &T::AccountId,
+        amount: T::Amount,
+    ) -> DispatchResult {
+        Self::do_approve(owner.clone(), delegate.clone(), asset, amount, None)
+    }
+
+    fn transfer_from(
+        asset: T::CollectionId,
+        owner: &T::AccountId,
+        delegate: &T::AccountId,
+        dest: &T::AccountId,
+        amount: T::Amount,
+    ) -> DispatchResult {
+        // The allowance is spent before the transfer runs, so a failing transfer must not keep
+        // the deduction.
+        frame_support::storage::with_storage_layer(|| {
+            Self::authorize_operator(owner, delegate, &[asset], &[amount])?;
+            Self::transfer_with_tax(
+                delegate.clone(),
+                owner.clone(),
+                dest.clone(),
+                vec![asset],
+                vec![amount],
+            )
+        })
+    }
+}
@@ -12,6 +12,10 @@
 extern crate alloc;
 
 pub use pallet::*;
+pub use pallet_multitoken_runtime_api::AuthorizationResult;
+
+/// Target of the log messages of the pallet.
+pub const LOG_TARGET: &str = "multitoken";
 
 #[cfg(test)]
 mod mock;
@@ -21,8 +25,12 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
-//pub mod weights;
-//pub use weights::*;
+mod impl_fungibles;
+pub mod migrations;
+mod types;
+pub use types::*;
+pub mod weights;
+pub use weights::*;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -32,15 +40,102 @@ pub mod pallet {
 
     use codec::Codec;
     use core::default::Default;
-    use frame_support::pallet_prelude::*;
+    use frame_support::{
+        pallet_prelude::*,
+        storage::PrefixIterator,
+        traits::{Contains, OnKilledAccount},
+    };
     use frame_system::pallet_prelude::*;
-    use sp_runtime::traits::AtLeast32BitUnsigned;
-    use sp_runtime::FixedPointOperand;
+    use sp_runtime::traits::{
+        AtLeast32BitUnsigned, CheckedAdd, CheckedSub, Hash, IdentifyAccount, One, Saturating,
+        TrailingZeroInput, Verify, Zero,
+    };
+    use sp_runtime::{
+        ArithmeticError, FixedPointNumber, FixedPointOperand, FixedU128, SaturatedConversion,
+    };
 
     use super::*;
 
+    /// Maximum number of holders returned by `top_holders`.
+    pub const MAX_TOP_HOLDERS: u32 = 100;
+
+    /// Tag at the start of the payloads signed for `permit_approval`, so that permits cannot be
+    /// mistaken for signatures over anything else.
+    pub const PERMIT_CONTEXT: &[u8] = b"multitoken:permit";
+
+    /// Tag at the start of the payloads signed for `claim_mint`, so that claims cannot be
+    /// mistaken for signatures over anything else.
+    pub const CLAIM_CONTEXT: &[u8] = b"multitoken:claim";
+
     pub trait Next {
         fn next(&self) -> Self;
+
+        /// Number of times `next` can still be called before the id space is exhausted, if known.
+        fn remaining(&self) -> Option<u128> {
+            None
+        }
+
+        /// Returns the next id, or `None` once the id space is exhausted.
+        fn checked_next(&self) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            (self.remaining() != Some(0)).then(|| self.next())
+        }
+    }
+
+    macro_rules! impl_next_for_integers {
+        ($($int:ty),*) => {$(
+            impl Next for $int {
+                /// Saturates at the maximum value, which is never handed out as an id.
+                fn next(&self) -> Self {
+                    self.saturating_add(1)
+                }
+
+                fn remaining(&self) -> Option<u128> {
+                    Some((<$int>::MAX - self) as u128)
+                }
+
+                fn checked_next(&self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+            }
+        )*};
+    }
+
+    impl_next_for_integers!(u8, u16, u32, u64, u128);
+
+    /// Transfer validation that collections can opt into by registering its index in
+    /// `CollectionHook`.
+    pub trait CollectionHooks<CollectionId, AccountId, Amount> {
+        /// Runs hook `hook` for a transfer of `amount` tokens of collection `id` from `from` to
+        /// `to`. `None` stands for a mint or a burn. Returning an error aborts the transfer.
+        fn on_transfer(
+            hook: u8,
+            id: &CollectionId,
+            from: Option<&AccountId>,
+            to: Option<&AccountId>,
+            amount: &Amount,
+        ) -> DispatchResult;
+    }
+
+    impl<CollectionId, AccountId, Amount> CollectionHooks<CollectionId, AccountId, Amount> for () {
+        fn on_transfer(
+            _hook: u8,
+            _id: &CollectionId,
+            _from: Option<&AccountId>,
+            _to: Option<&AccountId>,
+            _amount: &Amount,
+        ) -> DispatchResult {
+            Ok(())
+        }
+    }
+
+    /// Provides signed payloads to the benchmarks of extrinsics verifying off-chain signatures.
+    #[cfg(feature = "runtime-benchmarks")]
+    pub trait BenchmarkHelper<AccountId, Signature> {
+        /// Signs `payload` and returns the signer account along with the signature.
+        fn sign(payload: &[u8]) -> (AccountId, Signature);
     }
 
     #[pallet::config]
@@ -64,13 +159,127 @@ pub mod pallet {
             + TypeInfo
             + FixedPointOperand;
 
-        //// The weight information for this pallet.
-        // type WeightInfo: WeightInfo;
+        /// Maximum number of ids that can be processed in a single batch operation.
+        #[pallet::constant]
+        type MaxBatchSize: Get<u32>;
+
+        /// Maximum number of collections that can be created within a single block.
+        #[pallet::constant]
+        type MaxCreatesPerBlock: Get<u32>;
+
+        /// The weight information for this pallet.
+        type WeightInfo: WeightInfo;
+
+        /// Off-chain signature used by collection owners to authorize `claim_mint`.
+        type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+        /// Public key that identifies the signer of an `OffchainSignature`.
+        type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+        /// Helper used by the benchmarks to produce valid signatures.
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<Self::AccountId, Self::OffchainSignature>;
+
+        /// Maximum amount that can be minted at once by a single `mint`, `mint_batch` entry or
+        /// `mint_and_distribute` recipient. `None` disables the limit.
+        #[pallet::constant]
+        type MaxMintPerCall: Get<Option<Self::Amount>>;
+
+        /// Whether `HolderExited` is emitted when the balance of a sender drops to zero.
+        #[pallet::constant]
+        type EmitHolderExitEvents: Get<bool>;
+
+        /// Registry of the transfer hooks that collections can register through
+        /// `set_collection_hook`.
+        type CollectionHooks: CollectionHooks<Self::CollectionId, Self::AccountId, Self::Amount>;
+
+        /// Maximum length in bytes of the metadata URI of a collection.
+        #[pallet::constant]
+        type MaxUriLength: Get<u32>;
+
+        /// Maximum length in bytes of the name and symbol of a collection.
+        #[pallet::constant]
+        type StringLimit: Get<u32>;
+
+        /// Origin allowed to mint through `bridge_mint`.
+        type BridgeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Whether collections can be created with a caller-chosen id.
+        #[pallet::constant]
+        type CustomCollectionIds: Get<bool>;
+
+        /// Account that acts as a burn address: transfers to it destroy the tokens instead of
+        /// crediting it.
+        type NullAccount: Get<Option<Self::AccountId>>;
+
+        /// Whether URIs longer than `MaxUriLength` are rejected or truncated.
+        #[pallet::constant]
+        type UriTooLongPolicy: Get<UriPolicy>;
+
+        /// Account credited with the treasury share of transfer taxes.
+        type Treasury: Get<Self::AccountId>;
+
+        /// Whether transfer events are deposited with topics, so that clients can filter them by
+        /// collection and account.
+        #[pallet::constant]
+        type TransferEventTopics: Get<bool>;
+
+        /// Maximum number of inflation schedules minted in a single block. Schedules beyond it
+        /// are minted in the following blocks.
+        #[pallet::constant]
+        type MaxInflationsPerBlock: Get<u32>;
+
+        /// Accounts allowed to create collections. Use `Everything` to let anyone create them.
+        type CreateFilter: Contains<Self::AccountId>;
+
+        /// Maximum length of the memo attached to `safe_transfer_from_with_memo`.
+        #[pallet::constant]
+        type MaxMemoLength: Get<u32>;
+
+        /// Whether the last `TransferHistoryLen` transfers of each collection are kept on-chain.
+        #[pallet::constant]
+        type KeepTransferHistory: Get<bool>;
+
+        /// Number of transfers kept per collection when `KeepTransferHistory` is enabled.
+        #[pallet::constant]
+        type TransferHistoryLen: Get<u32>;
+
+        /// Whether minting to another account requires that account to have consented to the mints
+        /// of the minter through `set_mint_consent`.
+        #[pallet::constant]
+        type RequireMintConsent: Get<bool>;
+
+        /// Whether `OperatorTransfer` is emitted when an approved operator moves tokens on behalf
+        /// of their owner.
+        #[pallet::constant]
+        type EmitOperatorEvents: Get<bool>;
+
+        /// Maximum number of approvals removed by a single `clear_all_approvals` call.
+        #[pallet::constant]
+        type MaxApprovalsCleared: Get<u32>;
+
+        /// Maximum number of delegations between an owner's delegable operator and the deepest
+        /// sub-operator.
+        #[pallet::constant]
+        type MaxDelegationDepth: Get<u32>;
+
+        /// Accounts that can be approved as operators. Use `Everything` to allow any account.
+        type OperatorFilter: Contains<Self::AccountId>;
     }
 
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
+    /// Events of the pallet.
+    ///
+    /// Indexers decode past events with the current metadata, so the SCALE layout of the
+    /// existing events is part of the pallet's interface: new variants are appended at the end of
+    /// the enum and new fields after the existing ones of a variant, never inserted between them,
+    /// reordered or removed. Old clients then keep decoding the fields they know about.
     #[pallet::event]
     #[pallet::generate_deposit(pub (super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -86,14 +295,18 @@ pub mod pallet {
             to: Option<T::AccountId>,
             id: T::CollectionId,
             value: T::Amount,
+            /// Whether collection `id` is fungible.
+            fungible: bool,
         },
         /// Equivalent to multiple `TransferSingle` events, where `operator`, `from` and `to` are the same for all transfers.
         TransferBatch {
             operator: T::AccountId,
             from: Option<T::AccountId>,
             to: Option<T::AccountId>,
-            ids: Vec<T::CollectionId>,
-            values: Vec<T::Amount>,
+            ids: BoundedVec<T::CollectionId, T::MaxBatchSize>,
+            values: BoundedVec<T::Amount, T::MaxBatchSize>,
+            /// Whether each collection in `ids` is fungible.
+            fungible: BoundedVec<bool, T::MaxBatchSize>,
         },
         /// Emitted when `account` grants or revokes permission to `operator` to transfer their tokens, according to `approved`.
         ApprovalForAll {
@@ -101,6 +314,250 @@ pub mod pallet {
             operator: T::AccountId,
             approved: bool,
         },
+        /// The off-chain metadata of collection `id` changed and should be re-indexed.
+        MetadataUpdated { id: T::CollectionId },
+        /// The owner of collection `id` allowed `account` to claim `amount` tokens.
+        ClaimAllowed {
+            id: T::CollectionId,
+            account: T::AccountId,
+            amount: T::Amount,
+        },
+        /// `account` was added to or removed from the recipient allowlist of collection `id`.
+        RecipientAllowed {
+            id: T::CollectionId,
+            account: T::AccountId,
+            allowed: bool,
+        },
+        /// The recipient allowlist of collection `id` was switched on or off.
+        RecipientAllowlistToggled { id: T::CollectionId, enforced: bool },
+        /// The balance of `account` for collection `id` dropped to zero.
+        HolderExited {
+            id: T::CollectionId,
+            account: T::AccountId,
+        },
+        /// The transfer hook of collection `id` was set, or removed if `hook` is `None`.
+        CollectionHookSet {
+            id: T::CollectionId,
+            hook: Option<u8>,
+        },
+        /// The balance of `account` for collection `id` was set by root from `old` to `new`.
+        BalanceForceSet {
+            id: T::CollectionId,
+            account: T::AccountId,
+            old: T::Amount,
+            new: T::Amount,
+        },
+        /// Collection `id` has been destroyed.
+        CollectionDestroyed { id: T::CollectionId },
+        /// Transfers of every collection were paused or resumed.
+        PauseSet { paused: bool },
+        /// Collection `id` was frozen or thawed.
+        CollectionFrozen { id: T::CollectionId, frozen: bool },
+        /// `account` was frozen or thawed for collection `id`.
+        AccountFrozen {
+            id: T::CollectionId,
+            account: T::AccountId,
+            frozen: bool,
+        },
+        /// The metadata URI of collection `id` was set to `uri`.
+        UriSet {
+            id: T::CollectionId,
+            uri: BoundedVec<u8, T::MaxUriLength>,
+        },
+        /// The name and symbol of collection `id` were set.
+        MetadataSet {
+            id: T::CollectionId,
+            name: BoundedVec<u8, T::StringLimit>,
+            symbol: BoundedVec<u8, T::StringLimit>,
+        },
+        /// The display scale of collection `id` was set to `scale` decimals.
+        ScaleSet { id: T::CollectionId, scale: u8 },
+        /// The bridge message `message_hash` was processed by `bridge_mint`.
+        BridgeMessageProcessed { message_hash: T::Hash },
+        /// `account` approved `operator` to transfer up to `cap` of its tokens in total.
+        CappedApprovalSet {
+            account: T::AccountId,
+            operator: T::AccountId,
+            cap: T::Amount,
+        },
+        /// Every balance of collection `id` was cleared by `force_clear_balances`.
+        CollectionCleared { id: T::CollectionId },
+        /// The transfer tax of collection `id` was set.
+        TransferTaxSet {
+            id: T::CollectionId,
+            tax: TransferTax,
+        },
+        /// `payer` paid `amount` tokens of collection `id` of transfer tax to `beneficiary`.
+        TransferTaxPaid {
+            id: T::CollectionId,
+            payer: T::AccountId,
+            beneficiary: T::AccountId,
+            amount: T::Amount,
+        },
+        /// The royalty of collection `id` was set to `bps` basis points paid to `recipient`.
+        RoyaltySet {
+            id: T::CollectionId,
+            recipient: T::AccountId,
+            bps: u16,
+        },
+        /// The royalties of collection `id` are now paid to `recipient`.
+        RoyaltyRecipientChanged {
+            id: T::CollectionId,
+            recipient: T::AccountId,
+        },
+        /// `buyer` bought `item_amount` tokens of collection `item_id` from `seller` for
+        /// `payment_amount` tokens of collection `payment_id`, of which `royalty` went to the
+        /// royalty recipient of the item.
+        SaleExecuted {
+            seller: T::AccountId,
+            buyer: T::AccountId,
+            item_id: T::CollectionId,
+            item_amount: T::Amount,
+            payment_id: T::CollectionId,
+            payment_amount: T::Amount,
+            royalty: T::Amount,
+        },
+        /// The maximum supply of collection `id` was set.
+        MaxSupplySet {
+            id: T::CollectionId,
+            max_supply: Option<T::Amount>,
+        },
+        /// The inflation schedule of collection `id` was set, as the amount minted every block
+        /// and its beneficiary.
+        InflationScheduleSet {
+            id: T::CollectionId,
+            schedule: Option<(T::Amount, T::AccountId)>,
+        },
+        /// A transfer of collection `id` carried `memo`. Emitted right after the transfer event.
+        TransferMemo {
+            id: T::CollectionId,
+            memo: BoundedVec<u8, T::MaxMemoLength>,
+        },
+        /// A call of a long-running `operation` on collection `id` processed `processed` holders.
+        /// `remaining` holders are left, counted up to the limit of the call.
+        BatchProgress {
+            operation: BatchOp,
+            id: T::CollectionId,
+            processed: u32,
+            remaining: u32,
+        },
+        /// `operator` moved `amount` tokens of collection `id` on behalf of `owner`.
+        OperatorTransfer {
+            operator: T::AccountId,
+            owner: T::AccountId,
+            id: T::CollectionId,
+            amount: T::Amount,
+        },
+        /// The transfers of collection `id` were paused or resumed.
+        CollectionPauseSet { id: T::CollectionId, paused: bool },
+        /// The account taking over the collections of reaped owners was set, or removed if
+        /// `account` is `None`.
+        OrphanFallbackSet { account: Option<T::AccountId> },
+        /// The owner of collection `id` was reaped and `owner` took the collection over.
+        OrphanedCollectionReassigned {
+            id: T::CollectionId,
+            owner: T::AccountId,
+        },
+        /// `account` granted or revoked permission to `operator` to transfer its tokens of
+        /// collection `id`, according to `approved`.
+        CollectionApproval {
+            account: T::AccountId,
+            operator: T::AccountId,
+            id: T::CollectionId,
+            approved: bool,
+        },
+        /// `owner` allowed `operator` to move `amount` of its tokens of collection `id`. An
+        /// `amount` of zero revokes the allowance.
+        Approval {
+            owner: T::AccountId,
+            operator: T::AccountId,
+            id: T::CollectionId,
+            amount: T::Amount,
+        },
+        /// `removed` approvals granted by `account` were revoked. If `complete` is false, some
+        /// are left and `clear_all_approvals` has to be called again.
+        AllApprovalsCleared {
+            account: T::AccountId,
+            removed: u32,
+            complete: bool,
+        },
+        /// `owner` allowed or forbade `operator` to register sub-operators for its tokens.
+        OperatorDelegableSet {
+            owner: T::AccountId,
+            operator: T::AccountId,
+            delegable: bool,
+        },
+        /// `delegator` allowed `sub_operator` to move up to `cap` of `owner`'s tokens, or revoked
+        /// it if `cap` is `None`.
+        SubOperatorSet {
+            owner: T::AccountId,
+            delegator: T::AccountId,
+            sub_operator: T::AccountId,
+            cap: Option<T::Amount>,
+        },
+        /// `owner` allowed `operator` to make a single transfer of up to `amount` of its tokens of
+        /// collection `id`. An `amount` of zero revokes it.
+        OneShotApproval {
+            owner: T::AccountId,
+            operator: T::AccountId,
+            id: T::CollectionId,
+            amount: T::Amount,
+        },
+        /// The approval granted by `account` to `operator` expired and was removed.
+        ApprovalExpired {
+            account: T::AccountId,
+            operator: T::AccountId,
+        },
+        /// The owner of collection `id` offered its ownership to `new_owner`.
+        OwnershipTransferProposed {
+            id: T::CollectionId,
+            new_owner: T::AccountId,
+        },
+        /// `owner` accepted the ownership of collection `id`.
+        OwnerChanged {
+            id: T::CollectionId,
+            owner: T::AccountId,
+        },
+        /// The owner of collection `id` handed its roles to `issuer`, `admin` and `freezer`.
+        TeamChanged {
+            id: T::CollectionId,
+            issuer: T::AccountId,
+            admin: T::AccountId,
+            freezer: T::AccountId,
+        },
+        /// The destruction of collection `id` started. Its tokens can no longer be moved.
+        DestructionStarted { id: T::CollectionId },
+        /// The balances of `accounts_destroyed` holders of collection `id` were removed.
+        AccountsDestroyed {
+            id: T::CollectionId,
+            accounts_destroyed: u32,
+        },
+        /// `approvals_destroyed` approvals for collection `id` were removed.
+        ApprovalsDestroyed {
+            id: T::CollectionId,
+            approvals_destroyed: u32,
+        },
+        /// Every holder of `source` has been moved to `target`, and `source` is being destroyed.
+        CollectionsMerged {
+            source: T::CollectionId,
+            target: T::CollectionId,
+        },
+        /// `account` consented to receive the tokens minted by `minter`, or withdrew its consent,
+        /// according to `consent`.
+        MintConsentSet {
+            account: T::AccountId,
+            minter: T::AccountId,
+            consent: bool,
+        },
+        /// `operator` minted tokens of collection `id` to each of `recipients`, along with the
+        /// amount each of them received. Equivalent to a `TransferSingle` mint per recipient.
+        MintDistributed {
+            operator: T::AccountId,
+            id: T::CollectionId,
+            recipients: BoundedVec<(T::AccountId, T::Amount), T::MaxBatchSize>,
+            /// Whether collection `id` is fungible.
+            fungible: bool,
+        },
     }
 
     #[pallet::error]
@@ -109,14 +566,99 @@ pub mod pallet {
         InvalidOperator,
         /// Operator does not have sufficient rights to move funds.
         InsufficientApprovalForAll,
-        /// Arrays have different length when invoking extrinsics.
+        /// Deprecated: superseded by `MismatchedArrayLength`, `EmptyBatch` and `BatchTooLarge`.
+        /// Kept so that the indices of the remaining errors do not change.
         InvalidArrayLength,
         /// User has not enough balance of a given token collection.
         InsufficientBalance,
         /// The collection does not exist.
         CollectionDoesNotExist,
-        /// The account is not the one that created the collection.
+        /// The account is not the one that created the collection, or does not hold the role of
+        /// its team required by the call.
         InvalidOwner,
+        /// The `ids` and `amounts` arrays have different lengths.
+        MismatchedArrayLength,
+        /// A batch operation was invoked without any ids.
+        EmptyBatch,
+        /// A batch operation was invoked with more than `MaxBatchSize` ids.
+        BatchTooLarge,
+        /// The maximum number of collections created in this block has been reached.
+        CreateRateLimited,
+        /// A collection cannot be merged into itself.
+        CannotMergeIntoItself,
+        /// The item at position `index` of a batch has not enough balance.
+        InsufficientBalanceInBatch { index: u16 },
+        /// The collection is not fungible, so its supply cannot exceed one.
+        NotFungible,
+        /// The account has nothing to claim for the collection.
+        NothingToClaim,
+        /// The recipient is not in the allowlist of the collection.
+        RecipientNotAllowed,
+        /// The signature does not match the payload or was not produced by the expected signer.
+        InvalidSignature,
+        /// The amount exceeds the maximum that can be minted in a single call.
+        MintAmountTooLarge,
+        /// Every collection id has already been used.
+        CollectionIdsExhausted,
+        /// The collection still has holders.
+        CollectionNotEmpty,
+        /// The tokens cannot be moved because of a pause or a freeze.
+        Frozen,
+        /// The URI is longer than `MaxUriLength`.
+        UriTooLong,
+        /// The name or symbol is not valid UTF-8, contains control characters or is longer than
+        /// `StringLimit`.
+        InvalidMetadata,
+        /// The bridge message has already been processed.
+        DuplicateBridgeMessage,
+        /// The transfer exceeds what is left of the approval cap of the operator.
+        ApprovalCapExceeded,
+        /// The caller already used this client nonce.
+        DuplicateClientNonce,
+        /// Collections cannot be created with a custom id.
+        CustomIdsDisabled,
+        /// A collection with the given id already exists.
+        CollectionAlreadyExists,
+        /// Tokens cannot be minted to the null account.
+        MintToNullAccount,
+        /// The shares of a transfer tax add up to more than 10_000 basis points.
+        InvalidTransferTax,
+        /// Royalties cannot exceed 10_000 basis points.
+        RoyaltyTooHigh,
+        /// The collection has no royalty.
+        NoRoyaltySet,
+        /// The supply of the collection would exceed its maximum supply.
+        MaxSupplyExceeded,
+        /// The account is not allowed to create collections.
+        CreationNotPermitted,
+        /// The recipient has not consented to the mints of the minter.
+        MintConsentRequired,
+        /// The transfers of the collection are paused.
+        CollectionPaused,
+        /// The transfer exceeds the allowance of the operator.
+        AllowanceExceeded,
+        /// The approval of the operator has expired.
+        ApprovalExpired,
+        /// The deadline of the permit has passed.
+        PermitExpired,
+        /// The caller cannot delegate rights over the tokens of the owner.
+        NotDelegator,
+        /// The delegation would exceed `MaxDelegationDepth`.
+        DelegationTooDeep,
+        /// The account cannot be approved as an operator.
+        OperatorNotAllowed,
+        /// The caller was not offered the ownership of the collection.
+        NotPendingOwner,
+        /// The collection is being destroyed.
+        Destroying,
+        /// The collection is not being destroyed.
+        NotDestroying,
+        /// The collection still has balances or approvals to remove through `destroy_accounts`.
+        DestroyIncomplete,
+        /// No collection id can be derived from the caller and salt.
+        InvalidCollectionId,
+        /// The call would not process anything with a `limit` of zero.
+        ZeroLimit,
     }
 
     /// Stores the `CollectionId` that is going to be used for the next collection.
@@ -125,10 +667,21 @@ pub mod pallet {
     #[pallet::getter(fn next_collection_id)]
     pub type NextCollectionId<T: Config> = StorageValue<_, T::CollectionId, ValueQuery>;
 
+    /// Number of collections created in the current block.
+    /// This gets reset at the beginning of every block.
+    #[pallet::storage]
+    pub type CreatesInBlock<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Details of every collection.
     #[pallet::storage]
-    #[pallet::getter(fn collections)]
-    pub type Collections<T: Config> =
-        CountedStorageMap<_, Twox64Concat, T::CollectionId, T::AccountId, OptionQuery>;
+    #[pallet::getter(fn collection_details)]
+    pub type CollectionsV2<T: Config> = CountedStorageMap<
+        _,
+        Twox64Concat,
+        T::CollectionId,
+        CollectionDetails<T::AccountId, T::Amount>,
+        OptionQuery,
+    >;
 
     /// Maps collection to account balance.
     #[pallet::storage]
@@ -143,7 +696,7 @@ pub mod pallet {
         OptionQuery,
     >;
 
-    /// Maps owner to operator approval.
+    /// Maps owner to the approval granted to each operator.
     #[pallet::storage]
     #[pallet::getter(fn operator_approvals)]
     pub type OperatorApprovals<T: Config> = StorageDoubleMap<
@@ -152,209 +705,3534 @@ pub mod pallet {
         T::AccountId,
         Twox64Concat,
         T::AccountId,
-        bool,
+        ApprovalRecord<BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// Amount of each collection that an account can claim through `claim_and_approve`.
+    /// Set by the collection owner.
+    #[pallet::storage]
+    #[pallet::getter(fn claimable)]
+    pub type Claimable<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::CollectionId,
+        Twox64Concat,
+        T::AccountId,
+        T::Amount,
         ValueQuery,
     >;
 
-    #[pallet::call]
-    impl<T: Config> Pallet<T> {
-        /// Grants or revokes permission to `operator` to transfer the caller's tokens, according to `approved`.
-        #[pallet::call_index(0)]
-        #[pallet::weight({0})]
-        pub fn set_approval_for_all(
-            origin: OriginFor<T>,
-            operator: T::AccountId,
-            approved: bool,
-        ) -> DispatchResult {
-            let owner = ensure_signed(origin)?;
-            ensure!(owner != operator, Error::<T>::InvalidOperator,);
-            OperatorApprovals::<T>::insert(owner.clone(), operator.clone(), approved);
-            Self::deposit_event(Event::<T>::ApprovalForAll {
-                account: owner,
-                operator,
-                approved,
-            });
-            Ok(())
-        }
+    /// Accounts allowed to receive tokens of each collection when its allowlist is enforced.
+    #[pallet::storage]
+    #[pallet::getter(fn recipient_allowlist)]
+    pub type RecipientAllowlist<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::CollectionId,
+        Twox64Concat,
+        T::AccountId,
+        bool,
+        ValueQuery,
+    >;
 
-        /// Transfers `amount` tokens of token type `id` from `from` to `to`.
-        #[pallet::call_index(1)]
-        #[pallet::weight({0})]
-        pub fn safe_transfer_from(
-            origin: OriginFor<T>,
-            from: T::AccountId,
-            to: T::AccountId,
-            id: T::CollectionId,
-            amount: T::Amount,
-        ) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
-            ensure!(from != sender, Error::<T>::InsufficientApprovalForAll);
-            Self::update(sender, Some(from), Some(to), vec![id], vec![amount])
-        }
+    /// Collections that only accept recipients present in `RecipientAllowlist`.
+    #[pallet::storage]
+    #[pallet::getter(fn recipient_allowlist_enforced)]
+    pub type RecipientAllowlistEnforced<T: Config> =
+        StorageMap<_, Twox64Concat, T::CollectionId, bool, ValueQuery>;
 
-        /// Version of `safe_transfer_from`.
-        #[pallet::call_index(2)]
-        #[pallet::weight({0})]
-        pub fn safe_batch_transfer_from(
-            origin: OriginFor<T>,
-            from: T::AccountId,
-            to: T::AccountId,
-            ids: Vec<T::CollectionId>,
-            amounts: Vec<T::Amount>,
-        ) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
-            ensure!(
-                from == sender || Self::is_approved_for_all(&from, &sender),
-                Error::<T>::InsufficientApprovalForAll
-            );
-            Self::update(sender, Some(from), Some(to), ids, amounts)
-        }
+    /// Nonce that the next `claim_mint` signature of each account has to commit to.
+    #[pallet::storage]
+    #[pallet::getter(fn claim_nonce)]
+    pub type ClaimNonces<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u64, ValueQuery>;
 
-        /// Mints `amount` new tokens of collection `id` to user `to`.
-        /// Only the root account can perform this action.
-        #[pallet::call_index(3)]
-        #[pallet::weight({0})]
-        pub fn mint(
-            origin: OriginFor<T>,
-            to: T::AccountId,
-            id: T::CollectionId,
-            amount: T::Amount,
-        ) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
-            let owner = Collections::<T>::get(id);
-            ensure!(owner.is_some(), Error::<T>::CollectionDoesNotExist);
-            ensure!(owner.unwrap() == sender, Error::<T>::InvalidOwner);
-            Self::update(sender, None, Some(to), vec![id], vec![amount])
-        }
+    /// Raw key of the last `Balances` entry visited by the zero balance sweep run in `on_idle`.
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub type ZeroBalanceSweepCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
 
-        /// Version of `mint`.
-        #[pallet::call_index(4)]
-        #[pallet::weight({0})]
-        pub fn mint_batch(
-            origin: OriginFor<T>,
-            to: T::AccountId,
-            ids: Vec<T::CollectionId>,
-            amounts: Vec<T::Amount>,
-        ) -> DispatchResult {
-            ensure_root(origin.clone())?;
-            let sender = ensure_signed(origin)?;
-            Self::update(sender, None, Some(to), ids, amounts)
-        }
+    /// Index of the `CollectionHooks` entry run on every transfer of each collection.
+    #[pallet::storage]
+    #[pallet::getter(fn collection_hook)]
+    pub type CollectionHook<T: Config> =
+        StorageMap<_, Twox64Concat, T::CollectionId, u8, OptionQuery>;
 
-        /// Burns `amount` of collection `id` that belong to `origin`.
-        #[pallet::call_index(5)]
-        #[pallet::weight({0})]
-        pub fn burn(
-            origin: OriginFor<T>,
-            id: T::CollectionId,
-            amount: T::Amount,
-        ) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
-            Self::update(sender.clone(), Some(sender), None, vec![id], vec![amount])
-        }
+    /// Whether transfers of every collection are paused.
+    #[pallet::storage]
+    #[pallet::getter(fn paused)]
+    pub type Paused<T: Config> = StorageValue<_, bool, ValueQuery>;
 
-        /// Version of `burn`
-        #[pallet::call_index(6)]
-        #[pallet::weight({0})]
-        pub fn burn_batch(
-            origin: OriginFor<T>,
-            ids: Vec<T::CollectionId>,
+    /// Accounts that cannot move their tokens of each collection.
+    #[pallet::storage]
+    #[pallet::getter(fn frozen_account)]
+    pub type FrozenAccounts<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::CollectionId,
+        Twox64Concat,
+        T::AccountId,
+        bool,
+        ValueQuery,
+    >;
+
+    /// Metadata URI of each collection.
+    #[pallet::storage]
+    #[pallet::getter(fn collection_uri)]
+    pub type CollectionUri<T: Config> =
+        StorageMap<_, Twox64Concat, T::CollectionId, BoundedVec<u8, T::MaxUriLength>, OptionQuery>;
+
+    /// Name and symbol of each collection.
+    #[pallet::storage]
+    #[pallet::getter(fn metadata)]
+    pub type Metadata<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::CollectionId,
+        CollectionMetadata<BoundedVec<u8, T::StringLimit>>,
+        OptionQuery,
+    >;
+
+    /// Number of collections owned by each account.
+    #[pallet::storage]
+    #[pallet::getter(fn owner_collection_count)]
+    pub type OwnerCollectionCount<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Reverse index of `OperatorApprovals`, mapping each operator to the accounts that
+    /// approved it.
+    #[pallet::storage]
+    pub type ApprovalsByOperator<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        T::AccountId,
+        (),
+        OptionQuery,
+    >;
+
+    /// Reaped accounts whose granted and received approvals are still to be removed in `on_idle`.
+    #[pallet::storage]
+    pub type ReapedAccounts<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, (), OptionQuery>;
+
+    /// Number of decimals that user interfaces shift the balances of each collection by.
+    /// Purely presentational: on-chain amounts are always raw.
+    #[pallet::storage]
+    #[pallet::getter(fn collection_scale)]
+    pub type CollectionScale<T: Config> =
+        StorageMap<_, Twox64Concat, T::CollectionId, u8, ValueQuery>;
+
+    /// Hashes of the bridge messages already processed by `bridge_mint`.
+    #[pallet::storage]
+    pub type ProcessedMints<T: Config> = StorageMap<_, Identity, T::Hash, (), OptionQuery>;
+
+    /// Amount that each capped operator can still transfer on behalf of an account, summed over
+    /// every collection. Operators without an entry are not capped.
+    #[pallet::storage]
+    #[pallet::getter(fn approval_cap)]
+    pub type ApprovalCaps<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        T::AccountId,
+        T::Amount,
+        OptionQuery,
+    >;
+
+    /// Client nonces already used by each account in `mint_idempotent`.
+    #[pallet::storage]
+    pub type ClientNonces<T: Config> =
+        StorageMap<_, Blake2_128Concat, (T::AccountId, u64), (), OptionQuery>;
+
+    /// Block at which each collection was created. Set once and never updated.
+    #[pallet::storage]
+    #[pallet::getter(fn created_at)]
+    pub type CreatedAt<T: Config> =
+        StorageMap<_, Twox64Concat, T::CollectionId, BlockNumberFor<T>, OptionQuery>;
+
+    /// Tax charged on the transfers of each collection. Untaxed collections have no entry.
+    #[pallet::storage]
+    #[pallet::getter(fn transfer_tax)]
+    pub type TransferTaxes<T: Config> =
+        StorageMap<_, Twox64Concat, T::CollectionId, TransferTax, OptionQuery>;
+
+    /// Amount of tokens of each collection ever burned.
+    #[pallet::storage]
+    #[pallet::getter(fn burned)]
+    pub type Burned<T: Config> =
+        StorageMap<_, Twox64Concat, T::CollectionId, T::Amount, ValueQuery>;
+
+    /// Royalty of each collection, if any.
+    #[pallet::storage]
+    #[pallet::getter(fn royalty)]
+    pub type Royalties<T: Config> =
+        StorageMap<_, Twox64Concat, T::CollectionId, RoyaltyInfo<T::AccountId>, OptionQuery>;
+
+    /// Id of the most recently created collection.
+    ///
+    /// Only tracked since this item was introduced: chains with older collections read `None`
+    /// until the next one is created.
+    #[pallet::storage]
+    #[pallet::getter(fn last_collection_id)]
+    pub type LastCollectionId<T: Config> = StorageValue<_, T::CollectionId, OptionQuery>;
+
+    /// Amount of tokens of each collection minted every block to a beneficiary.
+    #[pallet::storage]
+    #[pallet::getter(fn inflation_schedule)]
+    pub type InflationSchedule<T: Config> =
+        StorageMap<_, Twox64Concat, T::CollectionId, (T::Amount, T::AccountId), OptionQuery>;
+
+    /// Raw key of the last `InflationSchedule` entry minted, when a block could not mint all of
+    /// them.
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub type InflationCursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+    /// Last transfers of each collection as `(from, to, amount, block)`, oldest first. Only kept
+    /// when `KeepTransferHistory` is enabled.
+    #[pallet::storage]
+    #[pallet::getter(fn recent_transfers)]
+    pub type RecentTransfers<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::CollectionId,
+        BoundedVec<
+            (Option<T::AccountId>, Option<T::AccountId>, T::Amount, BlockNumberFor<T>),
+            T::TransferHistoryLen,
+        >,
+        ValueQuery,
+    >;
+
+    /// Collections whose transfers are paused, independently of the global `Paused` switch.
+    #[pallet::storage]
+    #[pallet::getter(fn collection_paused)]
+    pub type CollectionPaused<T: Config> =
+        StorageMap<_, Twox64Concat, T::CollectionId, bool, ValueQuery>;
+
+    /// Account that takes over the collections of reaped owners. Without it, their collections
+    /// are frozen instead.
+    #[pallet::storage]
+    #[pallet::getter(fn orphan_fallback)]
+    pub type OrphanFallback<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+    /// Reaped accounts whose collections are still to be handed over or frozen in `on_idle`.
+    #[pallet::storage]
+    pub type ReapedOwners<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, (), OptionQuery>;
+
+    /// Reaped owner whose collections are being handed over, along with the raw key of the last
+    /// `CollectionsV2` entry visited for it.
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub type OrphanSweepCursor<T: Config> = StorageValue<_, (T::AccountId, Vec<u8>), OptionQuery>;
+
+    /// Maps owner to the operators approved for each of its collections, on top of the operators
+    /// approved for all of them in `OperatorApprovals`, along with the last block in which each
+    /// approval is valid, if it ever expires.
+    #[pallet::storage]
+    pub type CollectionApprovals<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        (T::CollectionId, T::AccountId),
+        Option<BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// Maps owner to the amount of each collection that each operator can still move on its
+    /// behalf, on top of the unlimited approvals in `OperatorApprovals` and `CollectionApprovals`,
+    /// along with the last block in which each allowance is valid, if it ever expires.
+    #[pallet::storage]
+    pub type Allowances<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        (T::CollectionId, T::AccountId),
+        (T::Amount, Option<BlockNumberFor<T>>),
+        OptionQuery,
+    >;
+
+    /// Nonce that the next `permit_approval` signature of each owner has to commit to.
+    #[pallet::storage]
+    #[pallet::getter(fn permit_nonce)]
+    pub type PermitNonces<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u64, ValueQuery>;
+
+    /// Maps owner to the operators that can register sub-operators for its tokens.
+    #[pallet::storage]
+    pub type DelegableOperators<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        T::AccountId,
+        (),
+        OptionQuery,
+    >;
+
+    /// Maps owner to the sub-operators that can move its tokens, along with the rights delegated to
+    /// each of them.
+    #[pallet::storage]
+    #[pallet::getter(fn sub_operator)]
+    pub type SubOperators<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        T::AccountId,
+        SubDelegation<T::AccountId, T::Amount>,
+        OptionQuery,
+    >;
+
+    /// Maps owner to the single transfers that each operator can make on its behalf, with the
+    /// maximum amount of each of them. An entry is consumed by the first transfer it authorizes.
+    #[pallet::storage]
+    pub type OneShotApprovals<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        (T::CollectionId, T::AccountId),
+        T::Amount,
+        OptionQuery,
+    >;
+
+    /// Map being walked while pruning expired approvals, `0` for `OperatorApprovals`, `1` for
+    /// `CollectionApprovals` and `2` for `Allowances`, along with the raw key of the last entry
+    /// visited in it.
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub type ApprovalExpirySweepCursor<T: Config> = StorageValue<_, (u8, Vec<u8>), OptionQuery>;
+
+    /// Account that collection `id` is being handed to, once it accepts the ownership.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_owner)]
+    pub type PendingCollectionOwner<T: Config> =
+        StorageMap<_, Twox64Concat, T::CollectionId, T::AccountId, OptionQuery>;
+
+    /// Accounts holding the roles of collection `id`. The owner holds every role of the
+    /// collections missing from here.
+    #[pallet::storage]
+    #[pallet::getter(fn collection_team)]
+    pub type CollectionTeams<T: Config> =
+        StorageMap<_, Twox64Concat, T::CollectionId, CollectionTeam<T::AccountId>, OptionQuery>;
+
+    /// Collections being destroyed, along with how far their destruction went.
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub type CollectionDestroying<T: Config> =
+        StorageMap<_, Twox64Concat, T::CollectionId, DestroyStage, OptionQuery>;
+
+    /// Maps account to the minters it accepts tokens from when `RequireMintConsent` is enabled.
+    #[pallet::storage]
+    pub type MintConsent<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AccountId,
+        Twox64Concat,
+        T::AccountId,
+        (),
+        OptionQuery,
+    >;
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+            CreatesInBlock::<T>::kill();
+            T::DbWeight::get()
+                .writes(1)
+                .saturating_add(Self::process_inflation())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            migrations::v1::migrate::<T>()
+                .saturating_add(migrations::v3::migrate::<T>())
+                .saturating_add(migrations::v4::migrate::<T>())
+        }
+
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let used = migrations::v2::sweep_zero_balances::<T>(remaining_weight);
+            let used = used.saturating_add(Self::cleanup_reaped_approvals(
+                remaining_weight.saturating_sub(used),
+            ));
+            let used = used.saturating_add(Self::reassign_orphaned_collections(
+                remaining_weight.saturating_sub(used),
+            ));
+            used.saturating_add(Self::prune_expired_approvals(
+                remaining_weight.saturating_sub(used),
+            ))
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Grants or revokes permission to `operator` to transfer the caller's tokens, according to `approved`.
+        /// The approval is no longer valid after block `deadline`, if any.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::set_approval_for_all())]
+        pub fn set_approval_for_all(
+            origin: OriginFor<T>,
+            operator: T::AccountId,
+            approved: bool,
+            deadline: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            Self::do_set_approval_for_all(owner, operator, approved, deadline)
+        }
+
+        /// Transfers `amount` tokens of token type `id` from `from` to `to`.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::safe_transfer_from())]
+        pub fn safe_transfer_from(
+            origin: OriginFor<T>,
+            from: T::AccountId,
+            to: T::AccountId,
+            id: T::CollectionId,
+            amount: T::Amount,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::authorize_operator(&from, &sender, &[id], &[amount])?;
+            Self::transfer_with_tax(sender, from, to, vec![id], vec![amount])
+        }
+
+        /// Version of `safe_transfer_from`.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::safe_batch_transfer_from(ids.len() as u32))]
+        pub fn safe_batch_transfer_from(
+            origin: OriginFor<T>,
+            from: T::AccountId,
+            to: T::AccountId,
+            ids: Vec<T::CollectionId>,
+            amounts: Vec<T::Amount>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::authorize_operator(&from, &sender, &ids, &amounts)?;
+            Self::transfer_with_tax(sender, from, to, ids, amounts)
+        }
+
+        /// Mints `amount` new tokens of collection `id` to user `to`.
+        /// Only the issuer of the collection can perform this action.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::mint())]
+        pub fn mint(
+            origin: OriginFor<T>,
+            to: T::AccountId,
+            id: T::CollectionId,
+            amount: T::Amount,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_role(&id, &sender, CollectionRole::Issuer)?;
+            Self::ensure_mint_amount_allowed(&amount)?;
+            Self::ensure_mint_consent(&sender, &to)?;
+            Self::update(sender, None, Some(to), vec![id], vec![amount])
+        }
+
+        /// Version of `mint`. The caller must be the issuer of every collection in `ids`.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::mint_batch(ids.len() as u32))]
+        pub fn mint_batch(
+            origin: OriginFor<T>,
+            to: T::AccountId,
+            ids: Vec<T::CollectionId>,
+            amounts: Vec<T::Amount>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            for id in ids.iter() {
+                Self::ensure_role(id, &sender, CollectionRole::Issuer)?;
+            }
+            for amount in amounts.iter() {
+                Self::ensure_mint_amount_allowed(amount)?;
+            }
+            Self::ensure_mint_consent(&sender, &to)?;
+            Self::update(sender, None, Some(to), ids, amounts)
+        }
+
+        /// Burns `amount` of collection `id` that belong to `origin`.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::burn())]
+        pub fn burn(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            amount: T::Amount,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::update(sender.clone(), Some(sender), None, vec![id], vec![amount])
+        }
+
+        /// Version of `burn`
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::burn_batch(ids.len() as u32))]
+        pub fn burn_batch(
+            origin: OriginFor<T>,
+            ids: Vec<T::CollectionId>,
+            amounts: Vec<T::Amount>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::update(sender.clone(), Some(sender), None, ids, amounts)
+        }
+
+        /// Creates a new collection.
+        /// Non-`fungible` collections behave as NFTs, so their total supply can never exceed one.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::create())]
+        pub fn create(origin: OriginFor<T>, fungible: bool) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::create_collection(sender, fungible)?;
+            Ok(())
+        }
+
+        /// Moves every holder's balance of `source` into `target`, adding it to any existing
+        /// `target` balance, as a burn of `source` and a mint of `target` for each holder. Both
+        /// collections must be owned by the caller and neither can be paused nor frozen for the
+        /// holders moved.
+        /// At most `limit` holders are moved per call; once `source` has no holders left
+        /// `CollectionsMerged` is emitted and `source` starts being destroyed, to be removed by
+        /// `destroy_accounts` and `finish_destroy`. Until then the call can be repeated.
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::merge_collections(*limit))]
+        pub fn merge_collections(
+            origin: OriginFor<T>,
+            source: T::CollectionId,
+            target: T::CollectionId,
+            limit: u32,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(source != target, Error::<T>::CannotMergeIntoItself);
+            let source_owner =
+                Self::collection_owner(&source).ok_or(Error::<T>::CollectionDoesNotExist)?;
+            let target_owner =
+                Self::collection_owner(&target).ok_or(Error::<T>::CollectionDoesNotExist)?;
+            ensure!(source_owner == sender && target_owner == sender, Error::<T>::InvalidOwner);
+            for id in [source, target] {
+                ensure!(!CollectionPaused::<T>::get(id), Error::<T>::CollectionPaused);
+                ensure!(!CollectionDestroying::<T>::contains_key(id), Error::<T>::Destroying);
+            }
+
+            let source_fungible = Self::fungible(source);
+            let target_fungible = Self::fungible(target);
+            let mut moved = T::Amount::zero();
+            let mut processed = 0u32;
+            for (account, amount) in Balances::<T>::drain_prefix(source).take(limit as usize) {
+                ensure!(!Self::is_frozen(&source, &account), Error::<T>::Frozen);
+                ensure!(!Self::is_frozen(&target, &account), Error::<T>::Frozen);
+                Self::ensure_recipient_allowed(&target, &account)?;
+                if let Some(hook) = CollectionHook::<T>::get(source) {
+                    T::CollectionHooks::on_transfer(hook, &source, Some(&account), None, &amount)?;
+                }
+                if let Some(hook) = CollectionHook::<T>::get(target) {
+                    T::CollectionHooks::on_transfer(hook, &target, None, Some(&account), &amount)?;
+                }
+                let balance = Self::balance_of(&account, &target)
+                    .checked_add(&amount)
+                    .ok_or(ArithmeticError::Overflow)?;
+                Self::set_balance(&target, &account, balance);
+                moved = moved.saturating_add(amount);
+                processed += 1;
+                Self::deposit_event(Event::<T>::TransferSingle {
+                    operator: sender.clone(),
+                    from: Some(account.clone()),
+                    to: None,
+                    id: source,
+                    value: amount,
+                    fungible: source_fungible,
+                });
+                Self::deposit_event(Event::<T>::TransferSingle {
+                    operator: sender.clone(),
+                    from: None,
+                    to: Some(account),
+                    id: target,
+                    value: amount,
+                    fungible: target_fungible,
+                });
+            }
+            Self::increase_supply(&target, moved)?;
+            Self::decrease_supply(&source, moved);
+            Self::deposit_batch_progress(BatchOp::MergeCollections, source, processed, limit);
+
+            if Balances::<T>::iter_prefix(source).next().is_none() {
+                CollectionDestroying::<T>::insert(source, DestroyStage::Accounts);
+                Self::deposit_event(Event::<T>::DestructionStarted { id: source });
+                Self::deposit_event(Event::<T>::CollectionsMerged { source, target });
+            }
+            Ok(())
+        }
+
+        /// Signals indexers that the off-chain metadata of collection `id` changed, mirroring
+        /// ERC4906's `MetadataUpdate`. Nothing is written to storage.
+        /// Only the admin of the collection can perform this action.
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::emit_metadata_update())]
+        pub fn emit_metadata_update(origin: OriginFor<T>, id: T::CollectionId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_role(&id, &sender, CollectionRole::Admin)?;
+            Self::deposit_event(Event::<T>::MetadataUpdated { id });
+            Ok(())
+        }
+
+        /// Allows `account` to claim `amount` tokens of collection `id` with `claim_and_approve`,
+        /// replacing any previous allowance. An `amount` of zero removes the allowance.
+        /// Only the issuer of the collection can perform this action.
+        #[pallet::call_index(10)]
+        #[pallet::weight(T::WeightInfo::allow_claim())]
+        pub fn allow_claim(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            account: T::AccountId,
+            amount: T::Amount,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_role(&id, &sender, CollectionRole::Issuer)?;
+            if amount.is_zero() {
+                Claimable::<T>::remove(id, &account);
+            } else {
+                ensure!(!CollectionDestroying::<T>::contains_key(id), Error::<T>::Destroying);
+                Claimable::<T>::insert(id, &account, amount);
+            }
+            Self::deposit_event(Event::<T>::ClaimAllowed {
+                id,
+                account,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Mints to the caller everything the owner of collection `id` allowed them to claim and
+        /// approves `operator` to transfer the caller's tokens, in a single transaction.
+        /// Since the approval is signed by the caller, the owner never acts on their behalf.
+        #[pallet::call_index(11)]
+        #[pallet::weight(T::WeightInfo::claim_and_approve())]
+        pub fn claim_and_approve(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            operator: T::AccountId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_not_self(&sender, &operator)?;
+            let amount = Claimable::<T>::take(id, &sender);
+            ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+            Self::update(sender.clone(), None, Some(sender.clone()), vec![id], vec![amount])?;
+            Self::do_set_approval_for_all(sender, operator, true, None)
+        }
+
+        /// Mints tokens of collection `id` directly to each of `recipients`, going through the same
+        /// checks and hooks as `mint` for each of them. Every recipient is checked before any
+        /// balance is credited, the supply is increased once and a single `MintDistributed` is
+        /// emitted.
+        /// Only the issuer of the collection can perform this action.
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::mint_and_distribute(recipients.len() as u32))]
+        pub fn mint_and_distribute(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            recipients: Vec<(T::AccountId, T::Amount)>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_role(&id, &sender, CollectionRole::Issuer)?;
+            ensure!(!recipients.is_empty(), Error::<T>::EmptyBatch);
+            let recipients = BoundedVec::<_, T::MaxBatchSize>::try_from(recipients)
+                .map_err(|_| Error::<T>::BatchTooLarge)?;
+            let details = CollectionsV2::<T>::get(id).ok_or(Error::<T>::CollectionDoesNotExist)?;
+            ensure!(!CollectionPaused::<T>::get(id), Error::<T>::CollectionPaused);
+            ensure!(!CollectionDestroying::<T>::contains_key(id), Error::<T>::Destroying);
+
+            let null_account = T::NullAccount::get();
+            let hook = CollectionHook::<T>::get(id);
+            let mut minted = T::Amount::zero();
+            for (to, amount) in recipients.iter() {
+                Self::ensure_mint_amount_allowed(amount)?;
+                Self::ensure_mint_consent(&sender, to)?;
+                ensure!(null_account.as_ref() != Some(to), Error::<T>::MintToNullAccount);
+                Self::ensure_recipient_allowed(&id, to)?;
+                if let Some(hook) = hook {
+                    T::CollectionHooks::on_transfer(hook, &id, None, Some(to), amount)?;
+                }
+                minted = minted
+                    .checked_add(amount)
+                    .ok_or(ArithmeticError::Overflow)?;
+            }
+            // Every balance is bounded by the supply, so none of them can overflow once the supply
+            // was increased.
+            Self::increase_supply(&id, minted)?;
+
+            for (to, amount) in recipients.iter() {
+                Self::set_balance(&id, to, Self::balance_of(to, &id).saturating_add(*amount));
+                if T::KeepTransferHistory::get() {
+                    Self::record_transfer(&id, None, Some(to.clone()), *amount);
+                }
+                log::debug!(
+                    target: LOG_TARGET,
+                    "minted {:?} of collection {:?} to {:?}",
+                    amount,
+                    id,
+                    to,
+                );
+            }
+
+            let topics = if T::TransferEventTopics::get() {
+                let accounts: Vec<_> = core::iter::once(&sender)
+                    .chain(recipients.iter().map(|(to, _)| to))
+                    .collect();
+                Self::transfer_topics(&[id], &accounts)
+            } else {
+                Vec::new()
+            };
+            let event: <T as Config>::RuntimeEvent = Event::<T>::MintDistributed {
+                operator: sender,
+                id,
+                recipients,
+                fungible: details.fungible,
+            }
+            .into();
+            frame_system::Pallet::<T>::deposit_event_indexed(&topics, event.into());
+            Ok(())
+        }
+
+        /// Adds `account` to, or removes it from, the recipient allowlist of collection `id`.
+        /// Only the admin of the collection can perform this action.
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::allow_recipient())]
+        pub fn allow_recipient(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            account: T::AccountId,
+            allowed: bool,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_role(&id, &sender, CollectionRole::Admin)?;
+            if allowed {
+                ensure!(!CollectionDestroying::<T>::contains_key(id), Error::<T>::Destroying);
+                RecipientAllowlist::<T>::insert(id, &account, true);
+            } else {
+                RecipientAllowlist::<T>::remove(id, &account);
+            }
+            Self::deposit_event(Event::<T>::RecipientAllowed {
+                id,
+                account,
+                allowed,
+            });
+            Ok(())
+        }
+
+        /// Switches the recipient allowlist of collection `id` on or off. While it is enforced,
+        /// transfers and mints to accounts outside the allowlist are rejected. Burns are always
+        /// allowed.
+        /// Only the admin of the collection can perform this action.
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::set_recipient_allowlist_enforced())]
+        pub fn set_recipient_allowlist_enforced(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            enforced: bool,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_role(&id, &sender, CollectionRole::Admin)?;
+            if enforced {
+                RecipientAllowlistEnforced::<T>::insert(id, true);
+            } else {
+                RecipientAllowlistEnforced::<T>::remove(id);
+            }
+            Self::deposit_event(Event::<T>::RecipientAllowlistToggled { id, enforced });
+            Ok(())
+        }
+
+        /// Mints `amount` tokens of collection `id` to the caller, as authorized off-chain by the
+        /// issuer of the collection. `signature` must be the issuer's signature over
+        /// `claim_payload`, which includes the caller's `claim_nonce`. The nonce gets incremented
+        /// so the same signature cannot be replayed.
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::claim_mint())]
+        pub fn claim_mint(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            amount: T::Amount,
+            signature: T::OffchainSignature,
+        ) -> DispatchResult {
+            let claimer = ensure_signed(origin)?;
+            let issuer = Self::role_holder(&id, CollectionRole::Issuer)?;
+            let nonce = ClaimNonces::<T>::get(&claimer);
+            let payload = Self::claim_payload(&id, &claimer, amount, nonce);
+            ensure!(signature.verify(&payload[..], &issuer), Error::<T>::InvalidSignature);
+            Self::ensure_mint_amount_allowed(&amount)?;
+            ClaimNonces::<T>::insert(&claimer, nonce.saturating_add(1));
+            Self::update(issuer, None, Some(claimer), vec![id], vec![amount])
+        }
+
+        /// Transfers `amount` tokens of token type `id` from the caller to `to`.
+        /// Shorthand for `safe_transfer_from` with the caller as `from`, so no approval is needed.
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::transfer())]
+        pub fn transfer(
+            origin: OriginFor<T>,
+            to: T::AccountId,
+            id: T::CollectionId,
+            amount: T::Amount,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::transfer_with_tax(sender.clone(), sender, to, vec![id], vec![amount])
+        }
+
+        /// Sets the `CollectionHooks` entry run on every transfer of collection `id`, or removes
+        /// it if `hook` is `None`.
+        /// Only the admin of the collection can perform this action.
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::set_collection_hook())]
+        pub fn set_collection_hook(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            hook: Option<u8>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_role(&id, &sender, CollectionRole::Admin)?;
+            CollectionHook::<T>::set(id, hook);
+            Self::deposit_event(Event::<T>::CollectionHookSet { id, hook });
+            Ok(())
+        }
+
+        /// Burns `amounts` tokens of each collection in `ids` from `from`.
+        /// The caller must be `from` or an operator approved by it.
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::burn_batch_from(ids.len() as u32))]
+        pub fn burn_batch_from(
+            origin: OriginFor<T>,
+            from: T::AccountId,
+            ids: Vec<T::CollectionId>,
+            amounts: Vec<T::Amount>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::authorize_operator(&from, &sender, &ids, &amounts)?;
+            Self::update(sender, Some(from), None, ids, amounts)
+        }
+
+        /// Sets the balance of `account` for collection `id` to `amount`, adjusting the total
+        /// supply of the collection by the difference. Meant for governance-driven corrections.
+        /// Can only be called by root.
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::force_set_balance())]
+        pub fn force_set_balance(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            account: T::AccountId,
+            amount: T::Amount,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(CollectionsV2::<T>::contains_key(id), Error::<T>::CollectionDoesNotExist);
+            ensure!(!CollectionDestroying::<T>::contains_key(id), Error::<T>::Destroying);
+            let old = Self::balance_of(&account, &id);
+            if amount > old {
+                Self::increase_supply(&id, amount - old)?;
+            } else {
+                Self::decrease_supply(&id, old - amount);
+            }
+            Self::set_balance(&id, &account, amount);
+            Self::deposit_event(Event::<T>::BalanceForceSet {
+                id,
+                account,
+                old,
+                new: amount,
+            });
+            Ok(())
+        }
+
+        /// Starts destroying collection `id`, which must not have any holders left. Its remaining
+        /// account entries and approvals are then removed by `destroy_accounts`, and the collection
+        /// itself by `finish_destroy`.
+        /// Only the owner of the collection can perform this action.
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::destroy_collection())]
+        pub fn destroy_collection(origin: OriginFor<T>, id: T::CollectionId) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &sender)?;
+            ensure!(
+                Balances::<T>::iter_prefix(id).next().is_none(),
+                Error::<T>::CollectionNotEmpty
+            );
+            ensure!(!CollectionDestroying::<T>::contains_key(id), Error::<T>::Destroying);
+            CollectionDestroying::<T>::insert(id, DestroyStage::Accounts);
+            Self::deposit_event(Event::<T>::DestructionStarted { id });
+            Ok(())
+        }
+
+        /// Pauses or resumes the transfers of every collection.
+        /// Can only be called by root.
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::set_paused())]
+        pub fn set_paused(origin: OriginFor<T>, paused: bool) -> DispatchResult {
+            ensure_root(origin)?;
+            Paused::<T>::put(paused);
+            Self::deposit_event(Event::<T>::PauseSet { paused });
+            Ok(())
+        }
+
+        /// Freezes or thaws collection `id`. Tokens of a frozen collection cannot be moved.
+        /// Only the freezer of the collection can perform this action.
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::freeze_collection())]
+        pub fn freeze_collection(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            frozen: bool,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_role(&id, &sender, CollectionRole::Freezer)?;
+            CollectionsV2::<T>::mutate(id, |details| {
+                if let Some(details) = details {
+                    details.frozen = frozen;
+                }
+            });
+            Self::deposit_event(Event::<T>::CollectionFrozen { id, frozen });
+            Ok(())
+        }
+
+        /// Freezes or thaws `account` for collection `id`. Frozen accounts cannot move their
+        /// tokens of the collection, but can still receive them.
+        /// Only the freezer of the collection can perform this action.
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::freeze_account())]
+        pub fn freeze_account(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            account: T::AccountId,
+            frozen: bool,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_role(&id, &sender, CollectionRole::Freezer)?;
+            if frozen {
+                ensure!(!CollectionDestroying::<T>::contains_key(id), Error::<T>::Destroying);
+                FrozenAccounts::<T>::insert(id, &account, true);
+            } else {
+                FrozenAccounts::<T>::remove(id, &account);
+            }
+            Self::deposit_event(Event::<T>::AccountFrozen {
+                id,
+                account,
+                frozen,
+            });
+            Ok(())
+        }
+
+        /// Sets the metadata URI of collection `id`. Frozen collections cannot change it.
+        /// Only the admin of the collection can perform this action.
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::WeightInfo::set_uri())]
+        pub fn set_uri(origin: OriginFor<T>, id: T::CollectionId, uri: Vec<u8>) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::do_set_uri(&sender, id, uri)
+        }
+
+        /// Version of `mint_batch` that also sets the metadata URI of each collection in `ids`
+        /// to the URI at the same position of `uris`.
+        #[pallet::call_index(25)]
+        #[pallet::weight(T::WeightInfo::mint_batch_with_uris(ids.len() as u32))]
+        pub fn mint_batch_with_uris(
+            origin: OriginFor<T>,
+            to: T::AccountId,
+            ids: Vec<T::CollectionId>,
+            amounts: Vec<T::Amount>,
+            uris: Vec<Vec<u8>>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(
+                ids.len() == amounts.len() && ids.len() == uris.len(),
+                Error::<T>::MismatchedArrayLength
+            );
+            ensure!(ids.len() <= T::MaxBatchSize::get() as usize, Error::<T>::BatchTooLarge);
+            for (id, uri) in ids.iter().zip(uris) {
+                Self::do_set_uri(&sender, *id, uri)?;
+            }
+            for amount in amounts.iter() {
+                Self::ensure_mint_amount_allowed(amount)?;
+            }
+            Self::ensure_mint_consent(&sender, &to)?;
+            Self::update(sender, None, Some(to), ids, amounts)
+        }
+
+        /// Sets the name and symbol of collection `id`. Both must be valid UTF-8 without control
+        /// characters. Frozen collections cannot change them.
+        /// Only the admin of the collection can perform this action.
+        #[pallet::call_index(26)]
+        #[pallet::weight(T::WeightInfo::set_metadata())]
+        pub fn set_metadata(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            name: Vec<u8>,
+            symbol: Vec<u8>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_role(&id, &sender, CollectionRole::Admin)?;
+            let details = CollectionsV2::<T>::get(id).ok_or(Error::<T>::CollectionDoesNotExist)?;
+            ensure!(!details.frozen, Error::<T>::Frozen);
+            let name = Self::validate_metadata_string(name)?;
+            let symbol = Self::validate_metadata_string(symbol)?;
+            Metadata::<T>::insert(
+                id,
+                CollectionMetadata {
+                    name: name.clone(),
+                    symbol: symbol.clone(),
+                },
+            );
+            Self::deposit_event(Event::<T>::MetadataSet { id, name, symbol });
+            Ok(())
+        }
+
+        /// Sets the number of decimals that user interfaces use to display the balances of
+        /// collection `id`. Balances and every on-chain computation are not affected.
+        /// Only the admin of the collection can perform this action.
+        #[pallet::call_index(27)]
+        #[pallet::weight(T::WeightInfo::set_scale())]
+        pub fn set_scale(origin: OriginFor<T>, id: T::CollectionId, scale: u8) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_role(&id, &sender, CollectionRole::Admin)?;
+            CollectionScale::<T>::insert(id, scale);
+            Self::deposit_event(Event::<T>::ScaleSet { id, scale });
+            Ok(())
+        }
+
+        /// Mints `amount` tokens of collection `id` to `to` on behalf of a bridge. Each
+        /// `message_hash` can only be processed once, so retried messages do not mint twice.
+        /// The collection owner is reported as the operator of the mint.
+        /// Can only be called by `BridgeOrigin`.
+        #[pallet::call_index(28)]
+        #[pallet::weight(T::WeightInfo::bridge_mint())]
+        pub fn bridge_mint(
+            origin: OriginFor<T>,
+            to: T::AccountId,
+            id: T::CollectionId,
+            amount: T::Amount,
+            message_hash: T::Hash,
+        ) -> DispatchResult {
+            T::BridgeOrigin::ensure_origin(origin)?;
+            ensure!(
+                !ProcessedMints::<T>::contains_key(message_hash),
+                Error::<T>::DuplicateBridgeMessage
+            );
+            let owner = Self::collection_owner(&id).ok_or(Error::<T>::CollectionDoesNotExist)?;
+            Self::ensure_mint_amount_allowed(&amount)?;
+            ProcessedMints::<T>::insert(message_hash, ());
+            Self::update(owner, None, Some(to), vec![id], vec![amount])?;
+            Self::deposit_event(Event::<T>::BridgeMessageProcessed { message_hash });
+            Ok(())
+        }
+
+        /// Approves `operator` to transfer the caller's tokens of any collection, up to `cap` in
+        /// total. Every transfer made by the operator is deducted from the cap.
+        /// `set_approval_for_all` replaces the approval, removing the cap.
+        #[pallet::call_index(29)]
+        #[pallet::weight(T::WeightInfo::set_capped_approval())]
+        pub fn set_capped_approval(
+            origin: OriginFor<T>,
+            operator: T::AccountId,
+            cap: T::Amount,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            Self::ensure_not_self(&owner, &operator)?;
+            Self::do_set_approval_for_all(owner.clone(), operator.clone(), true, None)?;
+            ApprovalCaps::<T>::insert(&owner, &operator, cap);
+            Self::deposit_event(Event::<T>::CappedApprovalSet {
+                account: owner,
+                operator,
+                cap,
+            });
+            Ok(())
+        }
+
+        /// Version of `mint` that can be safely retried: it fails if the caller already used
+        /// `client_nonce`, so resubmitting a dropped transaction never mints twice.
+        #[pallet::call_index(30)]
+        #[pallet::weight(T::WeightInfo::mint_idempotent())]
+        pub fn mint_idempotent(
+            origin: OriginFor<T>,
+            to: T::AccountId,
+            id: T::CollectionId,
+            amount: T::Amount,
+            client_nonce: u64,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let key = (sender.clone(), client_nonce);
+            ensure!(!ClientNonces::<T>::contains_key(&key), Error::<T>::DuplicateClientNonce);
+            ClientNonces::<T>::insert(&key, ());
+            Self::ensure_role(&id, &sender, CollectionRole::Issuer)?;
+            Self::ensure_mint_amount_allowed(&amount)?;
+            Self::ensure_mint_consent(&sender, &to)?;
+            Self::update(sender, None, Some(to), vec![id], vec![amount])
+        }
+
+        /// Mints `amount` tokens of collection `id` to `to`, first creating `id` as a fungible
+        /// collection owned by the caller if it does not exist yet. Creating requires custom
+        /// collection ids to be enabled.
+        /// If the collection exists, only its issuer can perform this action.
+        #[pallet::call_index(31)]
+        #[pallet::weight(T::WeightInfo::ensure_collection_and_mint())]
+        pub fn ensure_collection_and_mint(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            to: T::AccountId,
+            amount: T::Amount,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            if CollectionsV2::<T>::contains_key(id) {
+                Self::ensure_role(&id, &sender, CollectionRole::Issuer)?;
+            } else {
+                Self::create_collection_with_id(id, sender.clone(), true)?;
+            }
+            Self::ensure_mint_amount_allowed(&amount)?;
+            Self::ensure_mint_consent(&sender, &to)?;
+            Self::update(sender, None, Some(to), vec![id], vec![amount])
+        }
+
+        /// Transfers varying amounts of collection `id` from the caller to each of `recipients`.
+        /// The caller's balance must cover the whole distribution, which is checked before any
+        /// transfer takes place.
+        #[pallet::call_index(32)]
+        #[pallet::weight(T::WeightInfo::distribute(recipients.len() as u32))]
+        pub fn distribute(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            recipients: Vec<(T::AccountId, T::Amount)>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(!recipients.is_empty(), Error::<T>::EmptyBatch);
+            let recipients = BoundedVec::<_, T::MaxBatchSize>::try_from(recipients)
+                .map_err(|_| Error::<T>::BatchTooLarge)?;
+            let total = recipients
+                .iter()
+                .try_fold(T::Amount::zero(), |total, (_, amount)| {
+                    total.checked_add(amount).ok_or(ArithmeticError::Overflow)
+                })?;
+            ensure!(Self::balance_of(&sender, &id) >= total, Error::<T>::InsufficientBalance);
+            for (to, amount) in recipients {
+                Self::transfer_with_tax(
+                    sender.clone(),
+                    sender.clone(),
+                    to,
+                    vec![id],
+                    vec![amount],
+                )?;
+            }
+            Ok(())
+        }
+
+        /// Removes the balances of every holder of collection `id`, at most `limit` per call,
+        /// burning their tokens. Once no holders are left `CollectionCleared` is emitted. Until
+        /// then the call can be repeated. The configuration of the collection is kept.
+        /// Can only be called by root.
+        #[pallet::call_index(33)]
+        #[pallet::weight(T::WeightInfo::force_clear_balances(*limit))]
+        pub fn force_clear_balances(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            limit: u32,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(limit > 0, Error::<T>::ZeroLimit);
+            ensure!(CollectionsV2::<T>::contains_key(id), Error::<T>::CollectionDoesNotExist);
+            ensure!(!CollectionDestroying::<T>::contains_key(id), Error::<T>::Destroying);
+            let mut cleared = T::Amount::zero();
+            let mut processed = 0u32;
+            for (_, amount) in Balances::<T>::drain_prefix(id).take(limit as usize) {
+                cleared = cleared.saturating_add(amount);
+                processed += 1;
+            }
+            Self::decrease_supply(&id, cleared);
+            Burned::<T>::mutate(id, |burned| *burned = burned.saturating_add(cleared));
+            Self::deposit_batch_progress(BatchOp::ClearBalances, id, processed, limit);
+            if Balances::<T>::iter_key_prefix(id).next().is_none() {
+                Self::deposit_event(Event::<T>::CollectionCleared { id });
+            }
+            Ok(())
+        }
+
+        /// Sets the tax charged on every transfer of collection `id`. The tax is paid out of the
+        /// transferred tokens, so the recipient receives the amount minus both shares.
+        /// Only the owner of the collection can perform this action.
+        #[pallet::call_index(34)]
+        #[pallet::weight(T::WeightInfo::set_transfer_tax())]
+        pub fn set_transfer_tax(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            tax: TransferTax,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &sender)?;
+            ensure!(
+                tax.owner_bps.saturating_add(tax.treasury_bps) <= 10_000,
+                Error::<T>::InvalidTransferTax
+            );
+            if tax == TransferTax::default() {
+                TransferTaxes::<T>::remove(id);
+            } else {
+                TransferTaxes::<T>::insert(id, tax);
+            }
+            Self::deposit_event(Event::<T>::TransferTaxSet { id, tax });
+            Ok(())
+        }
+
+        /// Creates a new fungible collection whose id is derived from the caller and `salt`, so
+        /// that it can be known before submitting the transaction.
+        /// Only available when `CustomCollectionIds` is enabled.
+        #[pallet::call_index(35)]
+        #[pallet::weight(T::WeightInfo::create_deterministic())]
+        pub fn create_deterministic(origin: OriginFor<T>, salt: [u8; 32]) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            let id = Self::deterministic_collection_id(&sender, &salt)?;
+            Self::create_collection_with_id(id, sender, true)
+        }
+
+        /// Sets the royalty owed on the sales of collection `id` to `bps` basis points, paid to
+        /// `recipient`. A royalty of zero basis points removes it.
+        /// Only the owner of the collection can perform this action.
+        #[pallet::call_index(36)]
+        #[pallet::weight(T::WeightInfo::set_royalty())]
+        pub fn set_royalty(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            recipient: T::AccountId,
+            bps: u16,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &sender)?;
+            ensure!(bps <= 10_000, Error::<T>::RoyaltyTooHigh);
+            if bps == 0 {
+                Royalties::<T>::remove(id);
+            } else {
+                Royalties::<T>::insert(
+                    id,
+                    RoyaltyInfo {
+                        recipient: recipient.clone(),
+                        bps,
+                    },
+                );
+            }
+            Self::deposit_event(Event::<T>::RoyaltySet { id, recipient, bps });
+            Ok(())
+        }
+
+        /// Changes the recipient of the royalties of collection `id`, keeping its basis points.
+        /// Only the owner of the collection can perform this action.
+        #[pallet::call_index(37)]
+        #[pallet::weight(T::WeightInfo::set_royalty_recipient())]
+        pub fn set_royalty_recipient(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            new_recipient: T::AccountId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &sender)?;
+            Royalties::<T>::try_mutate(id, |royalty| -> DispatchResult {
+                let royalty = royalty.as_mut().ok_or(Error::<T>::NoRoyaltySet)?;
+                royalty.recipient = new_recipient.clone();
+                Ok(())
+            })?;
+            Self::deposit_event(Event::<T>::RoyaltyRecipientChanged {
+                id,
+                recipient: new_recipient,
+            });
+            Ok(())
+        }
+
+        /// Atomically transfers `item_amount` tokens of collection `item_id` from `seller` to
+        /// `buyer` and `payment_amount` tokens of collection `payment_id` from `buyer` to `seller`.
+        /// The royalty of the item collection, if any, is paid out of the payment.
+        /// The caller must be approved by both parties, unless it is one of them.
+        #[pallet::call_index(38)]
+        #[pallet::weight(T::WeightInfo::execute_sale())]
+        pub fn execute_sale(
+            origin: OriginFor<T>,
+            seller: T::AccountId,
+            buyer: T::AccountId,
+            item_id: T::CollectionId,
+            item_amount: T::Amount,
+            payment_id: T::CollectionId,
+            payment_amount: T::Amount,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::authorize_operator(&seller, &sender, &[item_id], &[item_amount])?;
+            Self::authorize_operator(&buyer, &sender, &[payment_id], &[payment_amount])?;
+            Self::transfer_with_tax(
+                sender.clone(),
+                seller.clone(),
+                buyer.clone(),
+                vec![item_id],
+                vec![item_amount],
+            )?;
+
+            let mut royalty = T::Amount::zero();
+            if let Some(info) = Royalties::<T>::get(item_id) {
+                royalty = Self::calculate_fee(payment_amount, info.bps);
+                if !royalty.is_zero() {
+                    Self::transfer_with_tax(
+                        sender.clone(),
+                        buyer.clone(),
+                        info.recipient,
+                        vec![payment_id],
+                        vec![royalty],
+                    )?;
+                }
+            }
+            Self::transfer_with_tax(
+                sender,
+                buyer.clone(),
+                seller.clone(),
+                vec![payment_id],
+                vec![payment_amount - royalty],
+            )?;
+            Self::deposit_event(Event::<T>::SaleExecuted {
+                seller,
+                buyer,
+                item_id,
+                item_amount,
+                payment_id,
+                payment_amount,
+                royalty,
+            });
+            Ok(())
+        }
+
+        /// Caps the supply of collection `id` at `max_supply`, or removes the cap if `None`. The
+        /// cap cannot be below the current supply.
+        /// Only the owner of the collection can perform this action.
+        #[pallet::call_index(39)]
+        #[pallet::weight(T::WeightInfo::set_max_supply())]
+        pub fn set_max_supply(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            max_supply: Option<T::Amount>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            CollectionsV2::<T>::try_mutate(id, |details| -> DispatchResult {
+                let details = details.as_mut().ok_or(Error::<T>::CollectionDoesNotExist)?;
+                ensure!(details.owner == sender, Error::<T>::InvalidOwner);
+                if let Some(max) = max_supply {
+                    ensure!(max >= details.total_supply, Error::<T>::MaxSupplyExceeded);
+                }
+                details.max_supply = max_supply;
+                Ok(())
+            })?;
+            Self::deposit_event(Event::<T>::MaxSupplySet { id, max_supply });
+            Ok(())
+        }
+
+        /// Mints `schedule.0` tokens of collection `id` to `schedule.1` at the start of every
+        /// block, until the maximum supply of the collection is reached. `None` removes the
+        /// schedule.
+        /// Only the owner of the collection can perform this action.
+        #[pallet::call_index(40)]
+        #[pallet::weight(T::WeightInfo::set_inflation_schedule())]
+        pub fn set_inflation_schedule(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            schedule: Option<(T::Amount, T::AccountId)>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &sender)?;
+            InflationSchedule::<T>::set(id, schedule.clone());
+            Self::deposit_event(Event::<T>::InflationScheduleSet { id, schedule });
+            Ok(())
+        }
+
+        /// Version of `safe_transfer_from` attaching `memo` to the transfer. The memo is only
+        /// emitted in a `TransferMemo` event, never stored.
+        #[pallet::call_index(41)]
+        #[pallet::weight(T::WeightInfo::safe_transfer_from_with_memo())]
+        pub fn safe_transfer_from_with_memo(
+            origin: OriginFor<T>,
+            from: T::AccountId,
+            to: T::AccountId,
+            id: T::CollectionId,
+            amount: T::Amount,
+            memo: BoundedVec<u8, T::MaxMemoLength>,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::authorize_operator(&from, &sender, &[id], &[amount])?;
+            Self::transfer_with_tax(sender, from, to, vec![id], vec![amount])?;
+            Self::deposit_event(Event::<T>::TransferMemo { id, memo });
+            Ok(())
+        }
+
+        /// Pauses the transfers of collection `id`, leaving every other collection untouched.
+        /// Can be called by the freezer of the collection or by root.
+        #[pallet::call_index(42)]
+        #[pallet::weight(T::WeightInfo::pause_collection())]
+        pub fn pause_collection(origin: OriginFor<T>, id: T::CollectionId) -> DispatchResult {
+            Self::do_set_collection_paused(origin, id, true)
+        }
+
+        /// Resumes the transfers of collection `id` paused by `pause_collection`.
+        /// Can be called by the freezer of the collection or by root.
+        #[pallet::call_index(43)]
+        #[pallet::weight(T::WeightInfo::unpause_collection())]
+        pub fn unpause_collection(origin: OriginFor<T>, id: T::CollectionId) -> DispatchResult {
+            Self::do_set_collection_paused(origin, id, false)
+        }
+
+        /// Sets the account that takes over the collections of reaped owners. While it is `None`,
+        /// the collections of reaped owners are frozen.
+        /// Can only be called by root.
+        #[pallet::call_index(44)]
+        #[pallet::weight(T::WeightInfo::set_orphan_fallback())]
+        pub fn set_orphan_fallback(
+            origin: OriginFor<T>,
+            account: Option<T::AccountId>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            OrphanFallback::<T>::set(account.clone());
+            Self::deposit_event(Event::<T>::OrphanFallbackSet { account });
+            Ok(())
+        }
+
+        /// Grants or revokes permission to `operator` to transfer the caller's tokens of collection
+        /// `id` only, according to `approved`. The approval is no longer valid after block
+        /// `deadline`, if any.
+        #[pallet::call_index(45)]
+        #[pallet::weight(T::WeightInfo::set_approval_for_collection())]
+        pub fn set_approval_for_collection(
+            origin: OriginFor<T>,
+            operator: T::AccountId,
+            id: T::CollectionId,
+            approved: bool,
+            deadline: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            Self::ensure_not_self(&owner, &operator)?;
+            if approved {
+                Self::ensure_operator_allowed(&operator)?;
+                ensure!(CollectionsV2::<T>::contains_key(id), Error::<T>::CollectionDoesNotExist);
+                ensure!(!CollectionDestroying::<T>::contains_key(id), Error::<T>::Destroying);
+                CollectionApprovals::<T>::insert(&owner, (id, &operator), deadline);
+            } else {
+                CollectionApprovals::<T>::remove(&owner, (id, &operator));
+            }
+            Self::deposit_event(Event::<T>::CollectionApproval {
+                account: owner,
+                operator,
+                id,
+                approved,
+            });
+            Ok(())
+        }
+
+        /// Allows `operator` to move up to `amount` of the caller's tokens of collection `id` until
+        /// block `deadline`, if any, replacing any previous allowance. Transfers by the operator
+        /// consume the allowance. An `amount` of zero revokes it.
+        #[pallet::call_index(46)]
+        #[pallet::weight(T::WeightInfo::approve())]
+        pub fn approve(
+            origin: OriginFor<T>,
+            operator: T::AccountId,
+            id: T::CollectionId,
+            amount: T::Amount,
+            deadline: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            Self::do_approve(owner, operator, id, amount, deadline)
+        }
+
+        /// Revokes every approval granted by the caller: approvals for all, approvals for single
+        /// collections, allowances, one-shot approvals and sub-operators, up to
+        /// `MaxApprovalsCleared` of them. Meant to cut every
+        /// operator off at once, for instance after a key compromise.
+        #[pallet::call_index(47)]
+        #[pallet::weight(T::WeightInfo::clear_all_approvals(T::MaxApprovalsCleared::get()))]
+        pub fn clear_all_approvals(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            let limit = T::MaxApprovalsCleared::get();
+            let mut removed = 0u32;
+            while removed < limit {
+                if let Some((operator, _)) = OperatorApprovals::<T>::drain_prefix(&owner).next() {
+                    ApprovalsByOperator::<T>::remove(&operator, &owner);
+                    ApprovalCaps::<T>::remove(&owner, &operator);
+                    DelegableOperators::<T>::remove(&owner, &operator);
+                } else if CollectionApprovals::<T>::drain_prefix(&owner)
+                    .next()
+                    .is_none()
+                    && Allowances::<T>::drain_prefix(&owner).next().is_none()
+                    && OneShotApprovals::<T>::drain_prefix(&owner).next().is_none()
+                    && SubOperators::<T>::drain_prefix(&owner).next().is_none()
+                {
+                    break;
+                }
+                removed += 1;
+            }
+            let complete = OperatorApprovals::<T>::iter_prefix(&owner).next().is_none()
+                && CollectionApprovals::<T>::iter_prefix(&owner)
+                    .next()
+                    .is_none()
+                && Allowances::<T>::iter_prefix(&owner).next().is_none()
+                && OneShotApprovals::<T>::iter_prefix(&owner).next().is_none()
+                && SubOperators::<T>::iter_prefix(&owner).next().is_none();
+            Self::deposit_event(Event::<T>::AllApprovalsCleared {
+                account: owner,
+                removed,
+                complete,
+            });
+            Ok(Some(T::WeightInfo::clear_all_approvals(removed)).into())
+        }
+
+        /// Increases the allowance of `operator` for the caller's tokens of collection `id` by
+        /// `added`, keeping its deadline. Unlike `approve`, it cannot race with a transfer that
+        /// spends the previous allowance. Expired allowances have to be renewed with `approve`.
+        #[pallet::call_index(48)]
+        #[pallet::weight(T::WeightInfo::increase_allowance())]
+        pub fn increase_allowance(
+            origin: OriginFor<T>,
+            operator: T::AccountId,
+            id: T::CollectionId,
+            added: T::Amount,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            Self::ensure_not_self(&owner, &operator)?;
+            Self::ensure_operator_allowed(&operator)?;
+            ensure!(CollectionsV2::<T>::contains_key(id), Error::<T>::CollectionDoesNotExist);
+            ensure!(!CollectionDestroying::<T>::contains_key(id), Error::<T>::Destroying);
+            let now = frame_system::Pallet::<T>::block_number();
+            let amount = Allowances::<T>::try_mutate(
+                &owner,
+                (id, &operator),
+                |allowance| -> Result<T::Amount, DispatchError> {
+                    let (amount, expires) = allowance.get_or_insert((Zero::zero(), None));
+                    ensure!(is_unexpired(expires, &now), Error::<T>::ApprovalExpired);
+                    *amount = amount
+                        .checked_add(&added)
+                        .ok_or(ArithmeticError::Overflow)?;
+                    Ok(*amount)
+                },
+            )?;
+            Self::deposit_event(Event::<T>::Approval {
+                owner,
+                operator,
+                id,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Decreases the allowance of `operator` for the caller's tokens of collection `id` by
+        /// `subtracted`, keeping its deadline. The allowance is removed once it reaches zero.
+        #[pallet::call_index(49)]
+        #[pallet::weight(T::WeightInfo::decrease_allowance())]
+        pub fn decrease_allowance(
+            origin: OriginFor<T>,
+            operator: T::AccountId,
+            id: T::CollectionId,
+            subtracted: T::Amount,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            let amount = Allowances::<T>::try_mutate_exists(
+                &owner,
+                (id, &operator),
+                |allowance| -> Result<T::Amount, DispatchError> {
+                    let (amount, _) = allowance.as_mut().ok_or(ArithmeticError::Underflow)?;
+                    *amount = amount
+                        .checked_sub(&subtracted)
+                        .ok_or(ArithmeticError::Underflow)?;
+                    let amount = *amount;
+                    if amount.is_zero() {
+                        *allowance = None;
+                    }
+                    Ok(amount)
+                },
+            )?;
+            Self::deposit_event(Event::<T>::Approval {
+                owner,
+                operator,
+                id,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Grants or revokes permission to `operator` to transfer `owner`'s tokens, according to
+        /// `approved`, as authorized off-chain by `owner`. Anyone can submit the permit, so that
+        /// the owner does not need to pay for a transaction.
+        ///
+        /// `signature` must be the owner's signature over `permit_payload`, which includes the
+        /// owner's `permit_nonce`. The nonce gets incremented so the same signature cannot be
+        /// replayed. The permit cannot be submitted after block `deadline`.
+        #[pallet::call_index(50)]
+        #[pallet::weight(T::WeightInfo::permit_approval())]
+        pub fn permit_approval(
+            origin: OriginFor<T>,
+            owner: T::AccountId,
+            operator: T::AccountId,
+            approved: bool,
+            deadline: BlockNumberFor<T>,
+            signature: T::OffchainSignature,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() <= deadline,
+                Error::<T>::PermitExpired
+            );
+            let nonce = PermitNonces::<T>::get(&owner);
+            let payload = Self::permit_payload(&owner, &operator, approved, nonce, deadline);
+            ensure!(signature.verify(&payload[..], &owner), Error::<T>::InvalidSignature);
+            PermitNonces::<T>::insert(&owner, nonce.saturating_add(1));
+            Self::do_set_approval_for_all(owner, operator, approved, None)
+        }
+
+        /// Allows or forbids `operator`, which must be approved for all of the caller's tokens, to
+        /// register sub-operators with capped rights over them.
+        #[pallet::call_index(51)]
+        #[pallet::weight(T::WeightInfo::set_operator_delegable())]
+        pub fn set_operator_delegable(
+            origin: OriginFor<T>,
+            operator: T::AccountId,
+            delegable: bool,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            Self::ensure_not_self(&owner, &operator)?;
+            if delegable {
+                ensure!(
+                    Self::is_approved_for_all(&owner, &operator),
+                    Error::<T>::InsufficientApprovalForAll
+                );
+                DelegableOperators::<T>::insert(&owner, &operator, ());
+            } else {
+                DelegableOperators::<T>::remove(&owner, &operator);
+            }
+            Self::deposit_event(Event::<T>::OperatorDelegableSet {
+                owner,
+                operator,
+                delegable,
+            });
+            Ok(())
+        }
+
+        /// Allows `sub_operator` to move up to `cap` of `owner`'s tokens on behalf of the caller,
+        /// or revokes it if `cap` is `None`.
+        ///
+        /// The caller must be a delegable operator of `owner` or one of its sub-operators. `cap`
+        /// cannot exceed the caller's own cap, or what is left of its approval cap if it is a
+        /// capped operator, and the delegation cannot get deeper than `MaxDelegationDepth`.
+        /// Sub-operators can be revoked by whoever registered them and by `owner`.
+        #[pallet::call_index(52)]
+        #[pallet::weight(T::WeightInfo::set_sub_operator())]
+        pub fn set_sub_operator(
+            origin: OriginFor<T>,
+            owner: T::AccountId,
+            sub_operator: T::AccountId,
+            cap: Option<T::Amount>,
+        ) -> DispatchResult {
+            let delegator = ensure_signed(origin)?;
+            Self::ensure_not_self(&owner, &sub_operator)?;
+            ensure!(delegator != sub_operator, Error::<T>::InvalidOperator);
+            match (SubOperators::<T>::get(&owner, &sub_operator), cap) {
+                (Some(existing), _) if existing.delegator == delegator => {}
+                (Some(_), None) if owner == delegator => {}
+                (None, Some(_)) => {}
+                _ => return Err(Error::<T>::NotDelegator.into()),
+            }
+            match cap {
+                Some(cap) => {
+                    Self::ensure_operator_allowed(&sub_operator)?;
+                    let depth = if Self::is_delegable_operator(&owner, &delegator) {
+                        if let Some(remaining) = ApprovalCaps::<T>::get(&owner, &delegator) {
+                            ensure!(cap <= remaining, Error::<T>::ApprovalCapExceeded);
+                        }
+                        1
+                    } else {
+                        let parent = SubOperators::<T>::get(&owner, &delegator)
+                            .filter(|parent| Self::is_valid_delegation(&owner, parent))
+                            .ok_or(Error::<T>::NotDelegator)?;
+                        ensure!(cap <= parent.cap, Error::<T>::ApprovalCapExceeded);
+                        parent.depth.saturating_add(1)
+                    };
+                    ensure!(depth <= T::MaxDelegationDepth::get(), Error::<T>::DelegationTooDeep);
+                    SubOperators::<T>::insert(
+                        &owner,
+                        &sub_operator,
+                        SubDelegation {
+                            delegator: delegator.clone(),
+                            cap,
+                            depth,
+                        },
+                    );
+                }
+                None => SubOperators::<T>::remove(&owner, &sub_operator),
+            }
+            Self::deposit_event(Event::<T>::SubOperatorSet {
+                owner,
+                delegator,
+                sub_operator,
+                cap,
+            });
+            Ok(())
+        }
+
+        /// Allows `operator` to make a single transfer of up to `amount` of the caller's tokens of
+        /// collection `id`, replacing any previous one-shot approval. The approval is consumed by
+        /// that transfer, whatever amount it moves. An `amount` of zero revokes it.
+        #[pallet::call_index(53)]
+        #[pallet::weight(T::WeightInfo::approve_transfer_once())]
+        pub fn approve_transfer_once(
+            origin: OriginFor<T>,
+            operator: T::AccountId,
+            id: T::CollectionId,
+            amount: T::Amount,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            Self::ensure_not_self(&owner, &operator)?;
+            if amount.is_zero() {
+                OneShotApprovals::<T>::remove(&owner, (id, &operator));
+            } else {
+                Self::ensure_operator_allowed(&operator)?;
+                ensure!(CollectionsV2::<T>::contains_key(id), Error::<T>::CollectionDoesNotExist);
+                ensure!(!CollectionDestroying::<T>::contains_key(id), Error::<T>::Destroying);
+                OneShotApprovals::<T>::insert(&owner, (id, &operator), amount);
+            }
+            Self::deposit_event(Event::<T>::OneShotApproval {
+                owner,
+                operator,
+                id,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Burns `amount` tokens of collection `id` from `from`.
+        /// The caller must be `from` or an operator approved by it.
+        #[pallet::call_index(54)]
+        #[pallet::weight(T::WeightInfo::burn_from())]
+        pub fn burn_from(
+            origin: OriginFor<T>,
+            from: T::AccountId,
+            id: T::CollectionId,
+            amount: T::Amount,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::authorize_operator(&from, &sender, &[id], &[amount])?;
+            Self::update(sender, Some(from), None, vec![id], vec![amount])
+        }
+
+        /// Grants or revokes permission to each operator in `approvals` to transfer the caller's
+        /// tokens, as `set_approval_for_all` would, emitting an `ApprovalForAll` for each of them.
+        /// Every entry is checked before any approval changes.
+        #[pallet::call_index(55)]
+        #[pallet::weight(T::WeightInfo::set_approvals(approvals.len() as u32))]
+        pub fn set_approvals(
+            origin: OriginFor<T>,
+            approvals: Vec<(T::AccountId, bool)>,
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            ensure!(!approvals.is_empty(), Error::<T>::EmptyBatch);
+            let approvals = BoundedVec::<_, T::MaxBatchSize>::try_from(approvals)
+                .map_err(|_| Error::<T>::BatchTooLarge)?;
+            for (operator, approved) in approvals.iter() {
+                Self::ensure_not_self(&owner, operator)?;
+                if *approved {
+                    Self::ensure_operator_allowed(operator)?;
+                }
+            }
+            for (operator, approved) in approvals {
+                Self::do_set_approval_for_all(owner.clone(), operator, approved, None)?;
+            }
+            Ok(())
+        }
+
+        /// Offers the ownership of collection `id` to `new_owner`, replacing any previous offer.
+        /// The ownership only changes hands once `new_owner` calls `accept_collection_ownership`.
+        /// Can only be called by the owner of the collection.
+        #[pallet::call_index(56)]
+        #[pallet::weight(T::WeightInfo::transfer_collection_ownership())]
+        pub fn transfer_collection_ownership(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            new_owner: T::AccountId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &sender)?;
+            PendingCollectionOwner::<T>::insert(id, &new_owner);
+            Self::deposit_event(Event::<T>::OwnershipTransferProposed { id, new_owner });
+            Ok(())
+        }
+
+        /// Accepts the ownership of collection `id`, previously offered to the caller through
+        /// `transfer_collection_ownership`. The team of the previous owner is dismissed, handing
+        /// every role to the caller. Collections being destroyed cannot change hands.
+        #[pallet::call_index(57)]
+        #[pallet::weight(T::WeightInfo::accept_collection_ownership())]
+        pub fn accept_collection_ownership(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(
+                PendingCollectionOwner::<T>::get(id).as_ref() == Some(&sender),
+                Error::<T>::NotPendingOwner
+            );
+            let mut details =
+                CollectionsV2::<T>::get(id).ok_or(Error::<T>::CollectionDoesNotExist)?;
+            ensure!(!CollectionDestroying::<T>::contains_key(id), Error::<T>::Destroying);
+            PendingCollectionOwner::<T>::remove(id);
+            OwnerCollectionCount::<T>::mutate(&details.owner, |count| {
+                *count = count.saturating_sub(1)
+            });
+            OwnerCollectionCount::<T>::mutate(&sender, |count| *count = count.saturating_add(1));
+            details.owner = sender.clone();
+            CollectionsV2::<T>::insert(id, details);
+            if CollectionTeams::<T>::take(id).is_some() {
+                Self::deposit_event(Event::<T>::TeamChanged {
+                    id,
+                    issuer: sender.clone(),
+                    admin: sender.clone(),
+                    freezer: sender.clone(),
+                });
+            }
+            Self::deposit_event(Event::<T>::OwnerChanged { id, owner: sender });
+            Ok(())
+        }
+
+        /// Delegates the minting, metadata administration and freezing of collection `id` to
+        /// `issuer`, `admin` and `freezer` respectively. The owner keeps the remaining rights and
+        /// can take the roles back by naming itself.
+        /// Can only be called by the owner of the collection.
+        #[pallet::call_index(58)]
+        #[pallet::weight(T::WeightInfo::set_team())]
+        pub fn set_team(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            issuer: T::AccountId,
+            admin: T::AccountId,
+            freezer: T::AccountId,
+        ) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+            Self::ensure_owner(&id, &sender)?;
+            if issuer == sender && admin == sender && freezer == sender {
+                CollectionTeams::<T>::remove(id);
+            } else {
+                let team = CollectionTeam {
+                    issuer: issuer.clone(),
+                    admin: admin.clone(),
+                    freezer: freezer.clone(),
+                };
+                CollectionTeams::<T>::insert(id, team);
+            }
+            Self::deposit_event(Event::<T>::TeamChanged {
+                id,
+                issuer,
+                admin,
+                freezer,
+            });
+            Ok(())
+        }
+
+        /// Starts destroying collection `id`, freezing its tokens. The collection is then torn
+        /// down by repeated calls to `destroy_accounts` and removed by `finish_destroy`.
+        /// Can be called by the owner of the collection or by root.
+        #[pallet::call_index(59)]
+        #[pallet::weight(T::WeightInfo::start_destroy())]
+        pub fn start_destroy(origin: OriginFor<T>, id: T::CollectionId) -> DispatchResult {
+            match ensure_signed_or_root(origin)? {
+                Some(sender) => Self::ensure_owner(&id, &sender)?,
+                None => {
+                    ensure!(
+                        CollectionsV2::<T>::contains_key(id),
+                        Error::<T>::CollectionDoesNotExist
+                    )
+                }
+            }
+            ensure!(!CollectionDestroying::<T>::contains_key(id), Error::<T>::Destroying);
+            CollectionDestroying::<T>::insert(id, DestroyStage::Accounts);
+            Self::deposit_event(Event::<T>::DestructionStarted { id });
+            Ok(())
+        }
+
+        /// Removes up to `witness.accounts` per-account entries of collection `id`: the balances of
+        /// its holders, burning their tokens, then its account freezes, allowlisted recipients and
+        /// claims. Once none are left, removes the approvals for the collection found among the
+        /// next `witness.approvals` approval entries. Can be repeated until the collection is ready
+        /// for `finish_destroy`.
+        /// Can be called by any account once `start_destroy` was called.
+        #[pallet::call_index(60)]
+        #[pallet::weight(T::WeightInfo::destroy_accounts(witness.accounts, witness.approvals))]
+        pub fn destroy_accounts(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            witness: DestroyWitness,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            let mut stage = CollectionDestroying::<T>::get(id).ok_or(Error::<T>::NotDestroying)?;
+            let mut accounts_destroyed = 0u32;
+            let mut entries_removed = 0u32;
+            if stage == DestroyStage::Accounts {
+                let mut burned = T::Amount::zero();
+                for (_, amount) in Balances::<T>::drain_prefix(id).take(witness.accounts as usize) {
+                    burned = burned.saturating_add(amount);
+                    accounts_destroyed += 1;
+                }
+                CollectionsV2::<T>::mutate(id, |details| {
+                    if let Some(details) = details {
+                        details.total_supply = details.total_supply.saturating_sub(burned);
+                    }
+                });
+                entries_removed = accounts_destroyed;
+                entries_removed += FrozenAccounts::<T>::drain_prefix(id)
+                    .take(witness.accounts.saturating_sub(entries_removed) as usize)
+                    .count() as u32;
+                entries_removed += RecipientAllowlist::<T>::drain_prefix(id)
+                    .take(witness.accounts.saturating_sub(entries_removed) as usize)
+                    .count() as u32;
+                entries_removed += Claimable::<T>::drain_prefix(id)
+                    .take(witness.accounts.saturating_sub(entries_removed) as usize)
+                    .count() as u32;
+                if Balances::<T>::iter_key_prefix(id).next().is_none()
+                    && FrozenAccounts::<T>::iter_key_prefix(id).next().is_none()
+                    && RecipientAllowlist::<T>::iter_key_prefix(id)
+                        .next()
+                        .is_none()
+                    && Claimable::<T>::iter_key_prefix(id).next().is_none()
+                {
+                    stage = DestroyStage::Approvals {
+                        map: 0,
+                        cursor: None,
+                    };
+                }
+                Self::deposit_event(Event::<T>::AccountsDestroyed {
+                    id,
+                    accounts_destroyed,
+                });
+            }
+
+            let mut approvals_visited = 0u32;
+            if let DestroyStage::Approvals { map, cursor } = &mut stage {
+                let mut budget = witness.approvals;
+                let mut approvals_destroyed = 0u32;
+                loop {
+                    *cursor = match *map {
+                        0 => Self::sweep_collection_approvals(
+                            &id,
+                            match cursor.take() {
+                                Some(cursor) => CollectionApprovals::<T>::iter_from(cursor),
+                                None => CollectionApprovals::<T>::iter(),
+                            },
+                            &mut budget,
+                            &mut approvals_destroyed,
+                            |owner, key| CollectionApprovals::<T>::remove(owner, key),
+                        ),
+                        1 => Self::sweep_collection_approvals(
+                            &id,
+                            match cursor.take() {
+                                Some(cursor) => Allowances::<T>::iter_from(cursor),
+                                None => Allowances::<T>::iter(),
+                            },
+                            &mut budget,
+                            &mut approvals_destroyed,
+                            |owner, key| Allowances::<T>::remove(owner, key),
+                        ),
+                        _ => Self::sweep_collection_approvals(
+                            &id,
+                            match cursor.take() {
+                                Some(cursor) => OneShotApprovals::<T>::iter_from(cursor),
+                                None => OneShotApprovals::<T>::iter(),
+                            },
+                            &mut budget,
+                            &mut approvals_destroyed,
+                            |owner, key| OneShotApprovals::<T>::remove(owner, key),
+                        ),
+                    };
+                    if cursor.is_some() || *map >= 2 {
+                        break;
+                    }
+                    *map += 1;
+                }
+                approvals_visited = witness.approvals.saturating_sub(budget);
+                Self::deposit_event(Event::<T>::ApprovalsDestroyed {
+                    id,
+                    approvals_destroyed,
+                });
+                if cursor.is_none() {
+                    stage = DestroyStage::Done;
+                }
+            }
+            CollectionDestroying::<T>::insert(id, stage);
+            Ok(Some(T::WeightInfo::destroy_accounts(entries_removed, approvals_visited)).into())
+        }
+
+        /// Removes collection `id` and everything left associated with it, once
+        /// `destroy_accounts` removed its balances and approvals.
+        /// Can be called by any account.
+        #[pallet::call_index(61)]
+        #[pallet::weight(T::WeightInfo::finish_destroy())]
+        pub fn finish_destroy(origin: OriginFor<T>, id: T::CollectionId) -> DispatchResult {
+            ensure_signed(origin)?;
+            let stage = CollectionDestroying::<T>::get(id).ok_or(Error::<T>::NotDestroying)?;
+            ensure!(stage == DestroyStage::Done, Error::<T>::DestroyIncomplete);
+            Self::cleanup_collection(&id);
+            Self::deposit_event(Event::<T>::CollectionDestroyed { id });
+            Ok(())
+        }
+
+        /// Consents to receive the tokens minted by `minter`, or withdraws the consent, according
+        /// to `consent`. Only needed when `RequireMintConsent` is enabled.
+        #[pallet::call_index(62)]
+        #[pallet::weight(T::WeightInfo::set_mint_consent())]
+        pub fn set_mint_consent(
+            origin: OriginFor<T>,
+            minter: T::AccountId,
+            consent: bool,
+        ) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+            if consent {
+                MintConsent::<T>::insert(&account, &minter, ());
+            } else {
+                MintConsent::<T>::remove(&account, &minter);
+            }
+            Self::deposit_event(Event::<T>::MintConsentSet {
+                account,
+                minter,
+                consent,
+            });
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Creates a new collection owned by `owner` and returns its id.
+        /// This is the building block of `create` and any other extrinsic creating collections.
+        ///
+        /// When custom ids are enabled, ids already taken by `create_collection_with_id` are
+        /// skipped.
+        pub fn create_collection(
+            owner: T::AccountId,
+            fungible: bool,
+        ) -> Result<T::CollectionId, DispatchError> {
+            ensure!(T::CreateFilter::contains(&owner), Error::<T>::CreationNotPermitted);
+            let mut collection_id = NextCollectionId::<T>::get();
+            ensure!(collection_id.remaining() != Some(0), Error::<T>::CollectionIdsExhausted);
+            if T::CustomCollectionIds::get() {
+                while CollectionsV2::<T>::contains_key(collection_id) {
+                    collection_id = collection_id.next();
+                    ensure!(
+                        collection_id.remaining() != Some(0),
+                        Error::<T>::CollectionIdsExhausted
+                    );
+                }
+            }
+            Self::insert_collection(collection_id, owner, fungible)?;
+            NextCollectionId::<T>::set(collection_id.next());
+            Ok(collection_id)
+        }
+
+        /// Creates a new collection with id `id` owned by `owner`.
+        /// Only available when `CustomCollectionIds` is enabled.
+        pub fn create_collection_with_id(
+            id: T::CollectionId,
+            owner: T::AccountId,
+            fungible: bool,
+        ) -> DispatchResult {
+            ensure!(T::CreateFilter::contains(&owner), Error::<T>::CreationNotPermitted);
+            ensure!(T::CustomCollectionIds::get(), Error::<T>::CustomIdsDisabled);
+            ensure!(!CollectionsV2::<T>::contains_key(id), Error::<T>::CollectionAlreadyExists);
+            Self::insert_collection(id, owner, fungible)
+        }
+
+        /// Returns the id of the collection that `who` creates with `create_deterministic` and
+        /// `salt`, derived from the hash of both.
+        pub fn deterministic_collection_id(
+            who: &T::AccountId,
+            salt: &[u8; 32],
+        ) -> Result<T::CollectionId, DispatchError> {
+            let hash = T::Hashing::hash_of(&(who, salt));
+            T::CollectionId::decode(&mut TrailingZeroInput::new(hash.as_ref()))
+                .map_err(|_| Error::<T>::InvalidCollectionId.into())
+        }
+
+        /// Stores a new collection `id` owned by `owner`, enforcing `MaxCreatesPerBlock`.
+        fn insert_collection(
+            id: T::CollectionId,
+            owner: T::AccountId,
+            fungible: bool,
+        ) -> DispatchResult {
+            let creates = CreatesInBlock::<T>::get();
+            ensure!(creates < T::MaxCreatesPerBlock::get(), Error::<T>::CreateRateLimited);
+            CreatesInBlock::<T>::put(creates + 1);
+            let details = CollectionDetails {
+                owner: owner.clone(),
+                total_supply: Zero::zero(),
+                max_supply: None,
+                frozen: false,
+                fungible,
+            };
+            CollectionsV2::<T>::insert(id, details);
+            CreatedAt::<T>::insert(id, frame_system::Pallet::<T>::block_number());
+            LastCollectionId::<T>::put(id);
+            OwnerCollectionCount::<T>::mutate(&owner, |count| *count = count.saturating_add(1));
+            Self::deposit_event(Event::<T>::CollectionCreated { id, owner });
+            Ok(())
+        }
+
+        /// Transfers `amount` tokens of token type `id` from `from` to `to`. Will mint (or burn) if `from` (or `to`) is `None`.
+        ///
+        /// Items are applied in order and each one reads the balances left by the previous ones,
+        /// so repeated ids accumulate: minting `[0, 0]` with `[10, 5]` credits 15, and moving more
+        /// than the sender holds across repeated ids fails at the first item that overdraws.
+        fn update(
+            operator: T::AccountId,
+            from: Option<T::AccountId>,
+            to: Option<T::AccountId>,
+            ids: Vec<T::CollectionId>,
             amounts: Vec<T::Amount>,
         ) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
-            Self::update(sender.clone(), Some(sender), None, ids, amounts)
+            ensure!(ids.len() == amounts.len(), Error::<T>::MismatchedArrayLength);
+            ensure!(!ids.is_empty(), Error::<T>::EmptyBatch);
+            let ids = BoundedVec::<_, T::MaxBatchSize>::try_from(ids)
+                .map_err(|_| Error::<T>::BatchTooLarge)?;
+            let amounts = BoundedVec::<_, T::MaxBatchSize>::try_from(amounts)
+                .map_err(|_| Error::<T>::BatchTooLarge)?;
+            let is_batch = ids.len() > 1;
+            // Transfers to the null account are burns, and minting to it would only create
+            // supply that nobody can spend.
+            let null_account = T::NullAccount::get();
+            if from.is_none() && to.is_some() {
+                ensure!(to != null_account, Error::<T>::MintToNullAccount);
+            }
+            let to = to.filter(|to| null_account.as_ref() != Some(to));
+            // Supply changes are aggregated per id so that the details of each collection are
+            // written once.
+            let mut supply_changes: Vec<(T::CollectionId, T::Amount)> = Vec::new();
+            let mut exited: Vec<T::CollectionId> = Vec::new();
+            let mut fungible: Vec<bool> = Vec::with_capacity(ids.len());
+            for (index, (id, amount)) in ids.iter().zip(amounts.iter()).enumerate() {
+                let details =
+                    CollectionsV2::<T>::get(id).ok_or(Error::<T>::CollectionDoesNotExist)?;
+                fungible.push(details.fungible);
+                ensure!(!CollectionPaused::<T>::get(id), Error::<T>::CollectionPaused);
+                ensure!(!CollectionDestroying::<T>::contains_key(id), Error::<T>::Destroying);
+
+                if let Some(from) = &from {
+                    ensure!(!Self::is_frozen(id, from), Error::<T>::Frozen);
+                    let from_balance = Self::balance_of(from, id);
+                    if from_balance < *amount {
+                        return Err(if is_batch {
+                            Error::<T>::InsufficientBalanceInBatch {
+                                index: index.saturated_into(),
+                            }
+                        } else {
+                            Error::<T>::InsufficientBalance
+                        }
+                        .into());
+                    }
+                    let from_balance = from_balance - *amount;
+                    Self::set_balance(id, from, from_balance);
+                    if from_balance.is_zero() && !exited.contains(id) {
+                        exited.push(*id);
+                    }
+                }
+
+                if let Some(hook) = CollectionHook::<T>::get(id) {
+                    T::CollectionHooks::on_transfer(hook, id, from.as_ref(), to.as_ref(), amount)?;
+                }
+
+                if let Some(to) = &to {
+                    Self::ensure_recipient_allowed(id, to)?;
+                    let to_balance = Self::balance_of(to, id)
+                        .checked_add(amount)
+                        .ok_or(ArithmeticError::Overflow)?;
+                    Self::set_balance(id, to, to_balance);
+                }
+
+                if T::KeepTransferHistory::get() {
+                    Self::record_transfer(id, from.clone(), to.clone(), *amount);
+                }
+
+                match (&from, &to) {
+                    (Some(from), Some(to)) => log::debug!(
+                        target: LOG_TARGET,
+                        "transferred {:?} of collection {:?} from {:?} to {:?}",
+                        amount,
+                        id,
+                        from,
+                        to,
+                    ),
+                    (None, Some(to)) => log::debug!(
+                        target: LOG_TARGET,
+                        "minted {:?} of collection {:?} to {:?}",
+                        amount,
+                        id,
+                        to,
+                    ),
+                    (Some(from), None) => log::debug!(
+                        target: LOG_TARGET,
+                        "burned {:?} of collection {:?} from {:?}",
+                        amount,
+                        id,
+                        from,
+                    ),
+                    (None, None) => {}
+                }
+
+                if from.is_none() || to.is_none() {
+                    match supply_changes.iter_mut().find(|(changed, _)| changed == id) {
+                        Some((_, total)) => {
+                            *total = total.checked_add(amount).ok_or(ArithmeticError::Overflow)?
+                        }
+                        None => supply_changes.push((*id, *amount)),
+                    }
+                }
+            }
+
+            for (id, amount) in supply_changes {
+                if from.is_none() {
+                    Self::increase_supply(&id, amount)?;
+                } else {
+                    Self::decrease_supply(&id, amount);
+                    Burned::<T>::mutate(id, |burned| *burned = burned.saturating_add(amount));
+                }
+            }
+
+            let topics = if T::TransferEventTopics::get() {
+                let accounts: Vec<_> = [Some(&operator), from.as_ref(), to.as_ref()]
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                Self::transfer_topics(&ids, &accounts)
+            } else {
+                Vec::new()
+            };
+            let delegated = from
+                .clone()
+                .filter(|from| T::EmitOperatorEvents::get() && *from != operator)
+                .map(|owner| (operator.clone(), owner, ids.clone(), amounts.clone()));
+            let event = if ids.len() == 1 {
+                Event::<T>::TransferSingle {
+                    operator,
+                    from: from.clone(),
+                    to,
+                    id: ids[0],
+                    value: amounts[0],
+                    fungible: fungible[0],
+                }
+            } else {
+                Event::<T>::TransferBatch {
+                    operator,
+                    from: from.clone(),
+                    to,
+                    ids,
+                    values: amounts,
+                    fungible: BoundedVec::truncate_from(fungible),
+                }
+            };
+            let event: <T as Config>::RuntimeEvent = event.into();
+            frame_system::Pallet::<T>::deposit_event_indexed(&topics, event.into());
+
+            if let Some((operator, owner, ids, amounts)) = delegated {
+                for (id, amount) in ids.into_iter().zip(amounts) {
+                    Self::deposit_event(Event::<T>::OperatorTransfer {
+                        operator: operator.clone(),
+                        owner: owner.clone(),
+                        id,
+                        amount,
+                    });
+                }
+            }
+
+            if let Some(account) = from.filter(|_| T::EmitHolderExitEvents::get()) {
+                for id in exited {
+                    // Transfers to oneself refill the balance that was just emptied.
+                    if Self::balance_of(&account, &id).is_zero() {
+                        Self::deposit_event(Event::<T>::HolderExited {
+                            id,
+                            account: account.clone(),
+                        });
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        /// Returns the topics of a transfer event of `ids` involving `accounts`: one per distinct
+        /// collection followed by one per distinct account.
+        pub fn transfer_topics(
+            ids: &[T::CollectionId],
+            accounts: &[&T::AccountId],
+        ) -> Vec<T::Hash> {
+            let mut topics = Vec::with_capacity(ids.len() + accounts.len());
+            let collections = ids
+                .iter()
+                .map(|id| T::Hashing::hash_of(&(b"multitoken/id", id)));
+            let accounts = accounts
+                .iter()
+                .map(|account| T::Hashing::hash_of(&(b"multitoken/account", account)));
+            for topic in collections.chain(accounts) {
+                if !topics.contains(&topic) {
+                    topics.push(topic);
+                }
+            }
+            topics
+        }
+
+        /// Appends a transfer to the history of collection `id`, evicting the oldest one if full.
+        fn record_transfer(
+            id: &T::CollectionId,
+            from: Option<T::AccountId>,
+            to: Option<T::AccountId>,
+            amount: T::Amount,
+        ) {
+            let now = frame_system::Pallet::<T>::block_number();
+            RecentTransfers::<T>::mutate(id, |history| {
+                if history.len() >= T::TransferHistoryLen::get() as usize && !history.is_empty() {
+                    history.remove(0);
+                }
+                let _ = history.try_push((from, to, amount, now));
+            });
+        }
+
+        /// Emits the `BatchProgress` of a call of `operation` on collection `id`. The remaining
+        /// holders are counted up to `limit`, so that the count costs no more than the call.
+        fn deposit_batch_progress(
+            operation: BatchOp,
+            id: T::CollectionId,
+            processed: u32,
+            limit: u32,
+        ) {
+            let remaining = Balances::<T>::iter_key_prefix(id)
+                .take(limit as usize)
+                .count()
+                .saturated_into();
+            Self::deposit_event(Event::<T>::BatchProgress {
+                operation,
+                id,
+                processed,
+                remaining,
+            });
+        }
+
+        /// Sets the balance of `who` for collection `id`, removing the entry when it drops to zero.
+        fn set_balance(id: &T::CollectionId, who: &T::AccountId, amount: T::Amount) {
+            if amount.is_zero() {
+                Balances::<T>::remove(id, who);
+            } else {
+                Balances::<T>::insert(id, who, amount);
+            }
+        }
+
+        /// Returns the amount of tokens of token type `id` owned by `account`.
+        pub fn balance_of(account: &T::AccountId, id: &T::CollectionId) -> T::Amount {
+            Balances::<T>::get(id, account).unwrap_or_default()
+        }
+
+        /// Version of `balance_of`. Returns `None` if `accounts` and `ids` have different lengths,
+        /// so empty inputs yield an empty list.
+        pub fn balance_of_batch(
+            accounts: &Vec<T::AccountId>,
+            ids: &Vec<T::CollectionId>,
+        ) -> Option<Vec<T::Amount>> {
+            if accounts.len() != ids.len() {
+                return None;
+            }
+            Some(
+                accounts
+                    .iter()
+                    .zip(ids.iter())
+                    .map(|(account, id)| Self::balance_of(account, id))
+                    .collect(),
+            )
+        }
+
+        /// Returns the weight of a `safe_batch_transfer_from` call moving `n` ids.
+        pub fn transfer_batch_weight(n: u32) -> Weight {
+            T::WeightInfo::safe_batch_transfer_from(n)
+        }
+
+        /// Returns how many `create` calls fit in `available` weight, so that clients can chunk
+        /// large batches of creations.
+        pub fn max_creates_for_weight(available: Weight) -> u32 {
+            available
+                .checked_div_per_component(&T::WeightInfo::create())
+                .map_or(u32::MAX, |count| count.saturated_into())
+        }
+
+        /// Returns true if `operator` is approved to transfer `account`'s tokens.
+        pub fn is_approved_for_all(account: &T::AccountId, operator: &T::AccountId) -> bool {
+            let now = frame_system::Pallet::<T>::block_number();
+            OperatorApprovals::<T>::get(account, operator)
+                .map(|record| record.is_active(now))
+                .unwrap_or_default()
+        }
+
+        /// Returns, in input order, whether each of `operators` is approved to transfer `account`'s tokens.
+        pub fn are_approved_for_all(
+            account: &T::AccountId,
+            operators: &[T::AccountId],
+        ) -> Vec<bool> {
+            operators
+                .iter()
+                .map(|operator| Self::is_approved_for_all(account, operator))
+                .collect()
+        }
+
+        /// Returns true if `operator` is approved for all of `owner`'s tokens and can register
+        /// sub-operators for them.
+        fn is_delegable_operator(owner: &T::AccountId, operator: &T::AccountId) -> bool {
+            DelegableOperators::<T>::contains_key(owner, operator)
+                && Self::is_approved_for_all(owner, operator)
+        }
+
+        /// Returns true if every delegator between `delegation` and `owner` still holds its
+        /// rights, so that revoking an operator also revokes the sub-operators below it.
+        fn is_valid_delegation(
+            owner: &T::AccountId,
+            delegation: &SubDelegation<T::AccountId, T::Amount>,
+        ) -> bool {
+            Self::delegation_root(owner, delegation).is_some()
+        }
+
+        /// Returns the sub-delegation of `operator` over `owner`'s tokens if it is still valid,
+        /// along with the delegable operator that started it and the approval cap of that
+        /// operator, if any.
+        fn active_sub_delegation(
+            owner: &T::AccountId,
+            operator: &T::AccountId,
+        ) -> Option<(SubDelegation<T::AccountId, T::Amount>, T::AccountId, Option<T::Amount>)>
+        {
+            let delegation = SubOperators::<T>::get(owner, operator)?;
+            let root = Self::delegation_root(owner, &delegation)?;
+            let root_cap = ApprovalCaps::<T>::get(owner, &root);
+            Some((delegation, root, root_cap))
+        }
+
+        /// Returns the delegable operator of `owner` at the top of `delegation`, if every
+        /// delegator between them still holds its rights.
+        fn delegation_root(
+            owner: &T::AccountId,
+            delegation: &SubDelegation<T::AccountId, T::Amount>,
+        ) -> Option<T::AccountId> {
+            let mut delegator = delegation.delegator.clone();
+            for _ in 0..T::MaxDelegationDepth::get() {
+                match SubOperators::<T>::get(owner, &delegator) {
+                    Some(parent) => delegator = parent.delegator,
+                    None => {
+                        return Self::is_delegable_operator(owner, &delegator).then_some(delegator)
+                    }
+                }
+            }
+            None
+        }
+
+        /// Returns the operators currently approved to transfer all of `account`'s tokens.
+        ///
+        /// `OperatorApprovals` is keyed by owner first, so this only visits the approvals granted
+        /// by `account`, expired ones included.
+        pub fn operators_of(account: &T::AccountId) -> Vec<T::AccountId> {
+            let now = frame_system::Pallet::<T>::block_number();
+            OperatorApprovals::<T>::iter_prefix(account)
+                .filter(|(_, record)| record.is_active(now))
+                .map(|(operator, _)| operator)
+                .collect()
+        }
+
+        /// Returns the owner of collection `id`, or `None` if it does not exist.
+        /// This is the canonical way of checking the existence and ownership of a collection.
+        pub fn collection_owner(id: &T::CollectionId) -> Option<T::AccountId> {
+            CollectionsV2::<T>::get(id).map(|details| details.owner)
+        }
+
+        /// Returns the owner of collection `id`, or `None` if it does not exist.
+        /// Kept for the callers of the getter of the former `Collections` storage.
+        pub fn collections(id: T::CollectionId) -> Option<T::AccountId> {
+            Self::collection_owner(&id)
+        }
+
+        /// Returns the amount of tokens of collection `id` in circulation.
+        pub fn total_supply(id: T::CollectionId) -> T::Amount {
+            CollectionsV2::<T>::get(id)
+                .map(|details| details.total_supply)
+                .unwrap_or_default()
+        }
+
+        /// Returns true if collection `id` exists and is fungible.
+        pub fn fungible(id: T::CollectionId) -> bool {
+            CollectionsV2::<T>::get(id).is_some_and(|details| details.fungible)
+        }
+
+        /// Version of `fungible` taking the id by reference, matching `is_collection_owner`.
+        pub fn is_fungible_collection(id: &T::CollectionId) -> bool {
+            Self::fungible(*id)
+        }
+
+        /// Returns true only if collection `id` exists and is owned by `who`.
+        pub fn is_collection_owner(id: &T::CollectionId, who: &T::AccountId) -> bool {
+            Self::collection_owner(id).as_ref() == Some(who)
+        }
+
+        /// Returns true if any account holds tokens of collection `id`. Zero balances are never
+        /// stored, so a single read of the `Balances` prefix suffices.
+        pub fn has_holders(id: &T::CollectionId) -> bool {
+            Balances::<T>::iter_prefix(id).next().is_some()
+        }
+
+        /// Returns true if every collection in `ids` exists. An empty slice trivially does.
+        pub fn all_exist(ids: &[T::CollectionId]) -> bool {
+            ids.iter().all(CollectionsV2::<T>::contains_key)
+        }
+
+        /// Returns every collection along with its owner.
+        ///
+        /// This iterates the whole `CollectionsV2` map and is therefore unbounded. It is meant for
+        /// off-chain and test usage only; runtime code should use `collections_up_to` instead.
+        pub fn all_collections() -> Vec<(T::CollectionId, T::AccountId)> {
+            CollectionsV2::<T>::iter()
+                .map(|(id, details)| (id, details.owner))
+                .collect()
+        }
+
+        /// Returns at most `limit` collections along with their owners.
+        ///
+        /// The result is a prefix of `all_collections`, so the cost is bounded by `limit` reads.
+        pub fn collections_up_to(limit: u32) -> Vec<(T::CollectionId, T::AccountId)> {
+            CollectionsV2::<T>::iter()
+                .take(limit as usize)
+                .map(|(id, details)| (id, details.owner))
+                .collect()
+        }
+
+        /// Ensures that collection `id` exists and is owned by `who`.
+        fn ensure_owner(id: &T::CollectionId, who: &T::AccountId) -> DispatchResult {
+            let owner = Self::collection_owner(id).ok_or(Error::<T>::CollectionDoesNotExist)?;
+            ensure!(&owner == who, Error::<T>::InvalidOwner);
+            Ok(())
+        }
+
+        /// Ensures that collection `id` exists and that `who` holds `role` in its team, which
+        /// falls back to the owner when no team was set.
+        fn ensure_role(
+            id: &T::CollectionId,
+            who: &T::AccountId,
+            role: CollectionRole,
+        ) -> DispatchResult {
+            let holder = Self::role_holder(id, role)?;
+            ensure!(&holder == who, Error::<T>::InvalidOwner);
+            Ok(())
         }
 
-        /// Creates a new collection
-        #[pallet::call_index(7)]
-        #[pallet::weight({0})]
-        pub fn create(origin: OriginFor<T>) -> DispatchResult {
-            let sender = ensure_signed(origin)?;
-            let collection_id = NextCollectionId::<T>::get();
-            Collections::<T>::insert(collection_id, sender.clone());
-            NextCollectionId::<T>::set(collection_id.next());
-            Self::deposit_event(Event::<T>::CollectionCreated {
-                id: collection_id,
-                owner: sender,
-            });
+        /// Returns the account holding `role` in the team of collection `id`, which falls back to
+        /// the owner when no team was set.
+        fn role_holder(
+            id: &T::CollectionId,
+            role: CollectionRole,
+        ) -> Result<T::AccountId, DispatchError> {
+            let owner = Self::collection_owner(id).ok_or(Error::<T>::CollectionDoesNotExist)?;
+            Ok(match CollectionTeams::<T>::get(id) {
+                Some(team) => match role {
+                    CollectionRole::Issuer => team.issuer,
+                    CollectionRole::Admin => team.admin,
+                    CollectionRole::Freezer => team.freezer,
+                },
+                None => owner,
+            })
+        }
+
+        /// Ensures that `operator` is not `owner` itself. Every approval has to go through this, as
+        /// accounts can already move their own tokens and self-approvals would only muddle caps.
+        fn ensure_not_self(owner: &T::AccountId, operator: &T::AccountId) -> DispatchResult {
+            ensure!(owner != operator, Error::<T>::InvalidOperator);
             Ok(())
         }
-    }
 
-    impl<T: Config> Pallet<T> {
-        /// Transfers `amount` tokens of token type `id` from `from` to `to`. Will mint (or burn) if `from` (or `to`) is `None`.
-        fn update(
+        /// Ensures that `operator` passes `OperatorFilter`. Every approval granted has to go
+        /// through this, while revoking approvals is always possible.
+        fn ensure_operator_allowed(operator: &T::AccountId) -> DispatchResult {
+            ensure!(T::OperatorFilter::contains(operator), Error::<T>::OperatorNotAllowed);
+            Ok(())
+        }
+
+        /// Grants or revokes permission to `operator` to transfer `owner`'s tokens.
+        fn do_set_approval_for_all(
+            owner: T::AccountId,
             operator: T::AccountId,
-            from: Option<T::AccountId>,
-            to: Option<T::AccountId>,
-            ids: Vec<T::CollectionId>,
-            amounts: Vec<T::Amount>,
+            approved: bool,
+            deadline: Option<BlockNumberFor<T>>,
         ) -> DispatchResult {
-            ensure!(ids.len() == amounts.len(), Error::<T>::InvalidArrayLength);
-            for i in 0..ids.len() {
-                let id = ids[i];
-                let amount = amounts[i];
+            Self::ensure_not_self(&owner, &operator)?;
+            if approved {
+                Self::ensure_operator_allowed(&operator)?;
+            }
+            ApprovalCaps::<T>::remove(&owner, &operator);
+            if approved {
+                let record = ApprovalRecord {
+                    global: true,
+                    expires: deadline,
+                };
+                OperatorApprovals::<T>::insert(&owner, &operator, record);
+                ApprovalsByOperator::<T>::insert(&operator, &owner, ());
+            } else {
+                OperatorApprovals::<T>::remove(&owner, &operator);
+                ApprovalsByOperator::<T>::remove(&operator, &owner);
+                DelegableOperators::<T>::remove(&owner, &operator);
+            }
+            Self::deposit_event(Event::<T>::ApprovalForAll {
+                account: owner,
+                operator,
+                approved,
+            });
+            Ok(())
+        }
 
-                if let Some(from) = &from {
-                    let from_balance =
-                        Balances::<T>::get(id, from).ok_or(<Error<T>>::CollectionDoesNotExist)?;
-                    ensure!(from_balance >= amount, Error::<T>::InsufficientBalance);
-                    Balances::<T>::insert(id, from, from_balance - amount);
+        /// Increases the total supply of collection `id` by `amount`, making sure that
+        /// non-fungible collections never exceed a supply of one.
+        fn increase_supply(id: &T::CollectionId, amount: T::Amount) -> DispatchResult {
+            CollectionsV2::<T>::try_mutate(id, |details| -> DispatchResult {
+                let details = details.as_mut().ok_or(Error::<T>::CollectionDoesNotExist)?;
+                details.total_supply = details
+                    .total_supply
+                    .checked_add(&amount)
+                    .ok_or(ArithmeticError::Overflow)?;
+                if let Some(max) = details.max_supply {
+                    ensure!(details.total_supply <= max, Error::<T>::MaxSupplyExceeded);
                 }
+                ensure!(
+                    details.total_supply <= T::Amount::one() || details.fungible,
+                    Error::<T>::NotFungible
+                );
+                Ok(())
+            })
+        }
 
-                if let Some(to) = &to {
-                    Balances::<T>::insert(id, to, amount);
+        /// Decreases the total supply of collection `id` by `amount`, if it exists.
+        fn decrease_supply(id: &T::CollectionId, amount: T::Amount) {
+            CollectionsV2::<T>::mutate(id, |details| {
+                if let Some(details) = details {
+                    details.total_supply = details.total_supply.saturating_sub(amount);
                 }
+            });
+        }
+
+        /// Ensures that `who` can receive tokens of collection `id`.
+        fn ensure_recipient_allowed(id: &T::CollectionId, who: &T::AccountId) -> DispatchResult {
+            ensure!(
+                !RecipientAllowlistEnforced::<T>::get(id) || RecipientAllowlist::<T>::get(id, who),
+                Error::<T>::RecipientNotAllowed
+            );
+            Ok(())
+        }
+
+        /// Ensures that `to` consented to receive tokens minted by `minter`, if `RequireMintConsent`
+        /// is enabled. Minting to oneself needs no consent.
+        fn ensure_mint_consent(minter: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+            if T::RequireMintConsent::get() && minter != to {
+                ensure!(
+                    MintConsent::<T>::contains_key(to, minter),
+                    Error::<T>::MintConsentRequired
+                );
             }
+            Ok(())
+        }
 
-            if ids.len() == 1 {
-                Self::deposit_event(Event::<T>::TransferSingle {
-                    operator,
-                    from,
-                    to,
-                    id: ids[0],
-                    value: amounts[0],
+        /// Ensures that `amount` does not exceed `MaxMintPerCall`.
+        fn ensure_mint_amount_allowed(amount: &T::Amount) -> DispatchResult {
+            if let Some(max) = T::MaxMintPerCall::get() {
+                ensure!(*amount <= max, Error::<T>::MintAmountTooLarge);
+            }
+            Ok(())
+        }
+
+        /// Number of collections that can still be created before the id space is exhausted.
+        /// `None` if the `CollectionId` type does not report its remaining ids.
+        pub fn remaining_collection_ids() -> Option<u128> {
+            NextCollectionId::<T>::get().remaining()
+        }
+
+        /// Returns `bps` basis points of `amount`, rounded down. `bps` is capped at 10_000.
+        /// Every fee and royalty computed by the pallet goes through here so that they all round
+        /// the same way.
+        pub fn calculate_fee(amount: T::Amount, bps: u16) -> T::Amount {
+            FixedU128::saturating_from_rational(bps.min(10_000), 10_000u16)
+                .saturating_mul_int(amount)
+        }
+
+        /// Removes collection `id` from every storage item referencing it.
+        ///
+        /// Every item keyed by collection alone has to be cleared here, so that destroyed or merged
+        /// collections leave no dangling entries behind. The per-account entries and approvals of
+        /// the collection must already be removed by `destroy_accounts`.
+        fn cleanup_collection(id: &T::CollectionId) {
+            debug_assert!(
+                Balances::<T>::iter_prefix(id).next().is_none(),
+                "collections are only cleaned up once they have no holders"
+            );
+            if let Some(details) = CollectionsV2::<T>::take(id) {
+                OwnerCollectionCount::<T>::mutate(&details.owner, |count| {
+                    *count = count.saturating_sub(1)
                 });
-            } else {
-                Self::deposit_event(Event::<T>::TransferBatch {
-                    operator,
-                    from,
-                    to,
-                    ids,
-                    values: amounts,
+            }
+            CollectionHook::<T>::remove(id);
+            CollectionUri::<T>::remove(id);
+            Metadata::<T>::remove(id);
+            CollectionScale::<T>::remove(id);
+            CreatedAt::<T>::remove(id);
+            TransferTaxes::<T>::remove(id);
+            Burned::<T>::remove(id);
+            Royalties::<T>::remove(id);
+            InflationSchedule::<T>::remove(id);
+            RecentTransfers::<T>::remove(id);
+            CollectionPaused::<T>::remove(id);
+            PendingCollectionOwner::<T>::remove(id);
+            CollectionTeams::<T>::remove(id);
+            CollectionDestroying::<T>::remove(id);
+            RecipientAllowlistEnforced::<T>::remove(id);
+        }
+
+        /// Returns true if `account` cannot currently move its tokens of collection `id`, either
+        /// because every transfer or the collection is paused, the collection is frozen or the
+        /// account is frozen for the collection.
+        pub fn is_frozen(id: &T::CollectionId, account: &T::AccountId) -> bool {
+            Paused::<T>::get()
+                || CollectionPaused::<T>::get(id)
+                || CollectionDestroying::<T>::contains_key(id)
+                || CollectionsV2::<T>::get(id).is_some_and(|details| details.frozen)
+                || FrozenAccounts::<T>::get(id, account)
+        }
+
+        /// Sets the metadata URI of collection `id` on behalf of `who`, which must be its admin.
+        fn do_set_uri(who: &T::AccountId, id: T::CollectionId, uri: Vec<u8>) -> DispatchResult {
+            Self::ensure_role(&id, who, CollectionRole::Admin)?;
+            let details = CollectionsV2::<T>::get(id).ok_or(Error::<T>::CollectionDoesNotExist)?;
+            ensure!(!details.frozen, Error::<T>::Frozen);
+            let uri: BoundedVec<u8, T::MaxUriLength> = match T::UriTooLongPolicy::get() {
+                UriPolicy::Reject => uri.try_into().map_err(|_| Error::<T>::UriTooLong)?,
+                UriPolicy::Truncate => BoundedVec::truncate_from(uri),
+            };
+            CollectionUri::<T>::insert(id, &uri);
+            Self::deposit_event(Event::<T>::UriSet { id, uri });
+            Ok(())
+        }
+
+        /// Transfers `amounts` of `ids` from `from` to `to`, paying the transfer tax of each
+        /// collection out of the transferred amounts. Shares owed to `from` itself stay with it.
+        pub(crate) fn transfer_with_tax(
+            operator: T::AccountId,
+            from: T::AccountId,
+            to: T::AccountId,
+            ids: Vec<T::CollectionId>,
+            mut amounts: Vec<T::Amount>,
+        ) -> DispatchResult {
+            ensure!(ids.len() == amounts.len(), Error::<T>::MismatchedArrayLength);
+            let mut payments = Vec::new();
+            for (id, amount) in ids.iter().zip(amounts.iter_mut()) {
+                if let Some(tax) = TransferTaxes::<T>::get(id) {
+                    let owner =
+                        Self::collection_owner(id).ok_or(Error::<T>::CollectionDoesNotExist)?;
+                    let gross = *amount;
+                    for (beneficiary, bps) in [
+                        (owner, tax.owner_bps),
+                        (T::Treasury::get(), tax.treasury_bps),
+                    ] {
+                        let share = Self::calculate_fee(gross, bps);
+                        *amount = amount.saturating_sub(share);
+                        if !share.is_zero() && beneficiary != from {
+                            payments.push((*id, beneficiary, share));
+                        }
+                    }
+                }
+            }
+            Self::update(operator.clone(), Some(from.clone()), Some(to), ids, amounts)?;
+            for (id, beneficiary, amount) in payments {
+                Self::update(
+                    operator.clone(),
+                    Some(from.clone()),
+                    Some(beneficiary.clone()),
+                    vec![id],
+                    vec![amount],
+                )?;
+                Self::deposit_event(Event::<T>::TransferTaxPaid {
+                    id,
+                    payer: from.clone(),
+                    beneficiary,
+                    amount,
                 });
             }
             Ok(())
         }
 
-        /// Returns the amount of tokens of token type `id` owned by `account`.
-        pub fn balance_of(account: &T::AccountId, id: &T::CollectionId) -> T::Amount {
-            Balances::<T>::get(id, account).unwrap_or_default()
+        /// Moves `amount` tokens of collection `id` from `from` to `to` on behalf of another pallet.
+        ///
+        /// This is a trusted internal API: it performs no approval checks and charges no fees or
+        /// royalties, so the calling pallet is responsible for authorizing the transfer. Freezes,
+        /// collection hooks and recipient allowlists still apply, and `from` is reported as the
+        /// operator of the emitted `TransferSingle`.
+        pub fn transfer_internal(
+            from: &T::AccountId,
+            to: &T::AccountId,
+            id: &T::CollectionId,
+            amount: T::Amount,
+        ) -> DispatchResult {
+            Self::update(
+                from.clone(),
+                Some(from.clone()),
+                Some(to.clone()),
+                vec![*id],
+                vec![amount],
+            )
         }
 
-        /// Version of `balance_of`.
-        pub fn balance_of_batch(
-            accounts: &Vec<T::AccountId>,
-            ids: &Vec<T::CollectionId>,
-        ) -> Option<Vec<T::Amount>> {
-            let len = accounts.len();
-            if len != ids.len() {
-                return None;
+        /// Best-effort version of `transfer_internal`.
+        ///
+        /// Returns whether the transfer went through. A failed attempt leaves no storage changes
+        /// behind, so callers can treat the transfer as optional.
+        pub fn try_transfer(
+            from: &T::AccountId,
+            to: &T::AccountId,
+            id: &T::CollectionId,
+            amount: T::Amount,
+        ) -> bool {
+            frame_support::storage::with_storage_layer(|| {
+                Self::transfer_internal(from, to, id, amount)
+            })
+            .is_ok()
+        }
+
+        /// Sets the allowance of `operator` over the tokens of collection `id` owned by `owner` to
+        /// `amount`, valid until `deadline`. A zero `amount` removes the allowance.
+        pub(crate) fn do_approve(
+            owner: T::AccountId,
+            operator: T::AccountId,
+            id: T::CollectionId,
+            amount: T::Amount,
+            deadline: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            Self::ensure_not_self(&owner, &operator)?;
+            if amount.is_zero() {
+                Allowances::<T>::remove(&owner, (id, &operator));
+            } else {
+                Self::ensure_operator_allowed(&operator)?;
+                ensure!(CollectionsV2::<T>::contains_key(id), Error::<T>::CollectionDoesNotExist);
+                ensure!(!CollectionDestroying::<T>::contains_key(id), Error::<T>::Destroying);
+                Allowances::<T>::insert(&owner, (id, &operator), (amount, deadline));
             }
-            let mut balances = Vec::with_capacity(len);
-            for i in 0..len {
-                balances[i] = Self::balance_of(&accounts[i], &ids[i]);
+            Self::deposit_event(Event::<T>::Approval {
+                owner,
+                operator,
+                id,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Ensures that `value` is valid UTF-8 without control characters and fits in
+        /// `StringLimit`.
+        fn validate_metadata_string(
+            value: Vec<u8>,
+        ) -> Result<BoundedVec<u8, T::StringLimit>, DispatchError> {
+            let text = core::str::from_utf8(&value).map_err(|_| Error::<T>::InvalidMetadata)?;
+            ensure!(!text.chars().any(char::is_control), Error::<T>::InvalidMetadata);
+            value
+                .try_into()
+                .map_err(|_| Error::<T>::InvalidMetadata.into())
+        }
+
+        /// Removes the approvals granted and received by the accounts in `ReapedAccounts`, as many
+        /// as `remaining_weight` allows, emitting `ApprovalForAll`, `CollectionApproval`, `Approval`,
+        /// `OneShotApproval` or `SubOperatorSet` for each of them. The approvals other than those
+        /// for all received by the accounts are not indexed and stay behind.
+        fn cleanup_reaped_approvals(remaining_weight: Weight) -> Weight {
+            let db_weight = T::DbWeight::get();
+            let per_approval = db_weight.reads_writes(1, 2);
+            let mut used = db_weight.reads(1);
+            while let Some(account) = ReapedAccounts::<T>::iter_keys().next() {
+                loop {
+                    if remaining_weight.any_lt(used.saturating_add(per_approval)) {
+                        return used;
+                    }
+                    used.saturating_accrue(per_approval);
+                    if let Some((operator, _)) =
+                        OperatorApprovals::<T>::drain_prefix(&account).next()
+                    {
+                        ApprovalsByOperator::<T>::remove(&operator, &account);
+                        ApprovalCaps::<T>::remove(&account, &operator);
+                        DelegableOperators::<T>::remove(&account, &operator);
+                        Self::deposit_event(Event::<T>::ApprovalForAll {
+                            account: account.clone(),
+                            operator,
+                            approved: false,
+                        });
+                    } else if let Some((owner, _)) =
+                        ApprovalsByOperator::<T>::drain_prefix(&account).next()
+                    {
+                        OperatorApprovals::<T>::remove(&owner, &account);
+                        ApprovalCaps::<T>::remove(&owner, &account);
+                        DelegableOperators::<T>::remove(&owner, &account);
+                        Self::deposit_event(Event::<T>::ApprovalForAll {
+                            account: owner,
+                            operator: account.clone(),
+                            approved: false,
+                        });
+                    } else if let Some(((id, operator), _)) =
+                        CollectionApprovals::<T>::drain_prefix(&account).next()
+                    {
+                        Self::deposit_event(Event::<T>::CollectionApproval {
+                            account: account.clone(),
+                            operator,
+                            id,
+                            approved: false,
+                        });
+                    } else if let Some(((id, operator), _)) =
+                        Allowances::<T>::drain_prefix(&account).next()
+                    {
+                        Self::deposit_event(Event::<T>::Approval {
+                            owner: account.clone(),
+                            operator,
+                            id,
+                            amount: Zero::zero(),
+                        });
+                    } else if let Some(((id, operator), _)) =
+                        OneShotApprovals::<T>::drain_prefix(&account).next()
+                    {
+                        Self::deposit_event(Event::<T>::OneShotApproval {
+                            owner: account.clone(),
+                            operator,
+                            id,
+                            amount: Zero::zero(),
+                        });
+                    } else if let Some((sub_operator, delegation)) =
+                        SubOperators::<T>::drain_prefix(&account).next()
+                    {
+                        Self::deposit_event(Event::<T>::SubOperatorSet {
+                            owner: account.clone(),
+                            delegator: delegation.delegator,
+                            sub_operator,
+                            cap: None,
+                        });
+                    } else {
+                        ReapedAccounts::<T>::remove(&account);
+                        break;
+                    }
+                }
+                used.saturating_accrue(db_weight.reads(1));
             }
-            Some(balances)
+            used
         }
 
-        /// Returns true if `operator` is approved to transfer `account`'s tokens.
-        pub fn is_approved_for_all(account: &T::AccountId, operator: &T::AccountId) -> bool {
-            OperatorApprovals::<T>::get(account, operator)
+        /// Hands the collections of the accounts in `ReapedOwners` over to `OrphanFallback`, or
+        /// freezes them if there is no fallback, as many as `remaining_weight` allows.
+        fn reassign_orphaned_collections(remaining_weight: Weight) -> Weight {
+            let db_weight = T::DbWeight::get();
+            let per_collection = db_weight.reads_writes(3, 4);
+            let mut used = db_weight.reads(1);
+            loop {
+                // An owner being handled is finished before any other, even if owners reaped
+                // since then come first in `ReapedOwners`.
+                let (owner, mut collections) = match OrphanSweepCursor::<T>::take() {
+                    Some((owner, cursor)) => (owner, CollectionsV2::<T>::iter_from(cursor)),
+                    None => match ReapedOwners::<T>::iter_keys().next() {
+                        Some(owner) => (owner, CollectionsV2::<T>::iter()),
+                        None => break,
+                    },
+                };
+                used.saturating_accrue(db_weight.reads_writes(2, 1));
+                // A reaped fallback cannot take over its own collections.
+                let fallback = OrphanFallback::<T>::get().filter(|fallback| fallback != &owner);
+                loop {
+                    if remaining_weight.any_lt(used.saturating_add(per_collection)) {
+                        let cursor = collections.last_raw_key().to_vec();
+                        OrphanSweepCursor::<T>::put((owner, cursor));
+                        return used;
+                    }
+                    used.saturating_accrue(per_collection);
+                    match collections.next() {
+                        Some((id, mut details)) if details.owner == owner => {
+                            if let Some(fallback) = &fallback {
+                                details.owner = fallback.clone();
+                                CollectionsV2::<T>::insert(id, details);
+                                PendingCollectionOwner::<T>::remove(id);
+                                OwnerCollectionCount::<T>::mutate(&owner, |count| {
+                                    *count = count.saturating_sub(1)
+                                });
+                                OwnerCollectionCount::<T>::mutate(fallback, |count| {
+                                    *count = count.saturating_add(1)
+                                });
+                                Self::deposit_event(Event::<T>::OrphanedCollectionReassigned {
+                                    id,
+                                    owner: fallback.clone(),
+                                });
+                            } else if !details.frozen {
+                                details.frozen = true;
+                                CollectionsV2::<T>::insert(id, details);
+                                Self::deposit_event(Event::<T>::CollectionFrozen {
+                                    id,
+                                    frozen: true,
+                                });
+                            }
+                        }
+                        Some(_) => {}
+                        None => break,
+                    }
+                }
+                ReapedOwners::<T>::remove(&owner);
+            }
+            used
         }
 
-        pub fn all_collections() -> Vec<(T::CollectionId, T::AccountId)> {
-            Collections::<T>::iter().collect()
+        /// Removes the expired records of `OperatorApprovals`, along with their caps, then the
+        /// expired entries of `CollectionApprovals` and `Allowances`, as many as `remaining_weight`
+        /// allows. Expired records emit `ApprovalExpired`, the other entries are reported as
+        /// revoked. The walk resumes from `ApprovalExpirySweepCursor` in the following blocks and
+        /// starts over once the three maps have been visited.
+        fn prune_expired_approvals(remaining_weight: Weight) -> Weight {
+            let db_weight = T::DbWeight::get();
+            let per_entry = db_weight.reads_writes(1, 4);
+            let mut used = db_weight.reads_writes(2, 1);
+            if remaining_weight.any_lt(used.saturating_add(per_entry)) {
+                return Weight::zero();
+            }
+            let now = frame_system::Pallet::<T>::block_number();
+            let (mut map, mut cursor) = match ApprovalExpirySweepCursor::<T>::take() {
+                Some((map, cursor)) => (map, Some(cursor)),
+                None => (0, None),
+            };
+            loop {
+                cursor = match map {
+                    0 => Self::prune_expired_entries(
+                        match cursor.take() {
+                            Some(cursor) => OperatorApprovals::<T>::iter_from(cursor),
+                            None => OperatorApprovals::<T>::iter(),
+                        },
+                        remaining_weight,
+                        &mut used,
+                        |account, operator, record| {
+                            if !is_unexpired(&record.expires, &now) {
+                                OperatorApprovals::<T>::remove(&account, &operator);
+                                ApprovalsByOperator::<T>::remove(&operator, &account);
+                                ApprovalCaps::<T>::remove(&account, &operator);
+                                DelegableOperators::<T>::remove(&account, &operator);
+                                Self::deposit_event(Event::<T>::ApprovalExpired {
+                                    account,
+                                    operator,
+                                });
+                            }
+                        },
+                    ),
+                    1 => Self::prune_expired_entries(
+                        match cursor.take() {
+                            Some(cursor) => CollectionApprovals::<T>::iter_from(cursor),
+                            None => CollectionApprovals::<T>::iter(),
+                        },
+                        remaining_weight,
+                        &mut used,
+                        |account, (id, operator), deadline| {
+                            if !is_unexpired(&deadline, &now) {
+                                CollectionApprovals::<T>::remove(&account, (id, &operator));
+                                Self::deposit_event(Event::<T>::CollectionApproval {
+                                    account,
+                                    operator,
+                                    id,
+                                    approved: false,
+                                });
+                            }
+                        },
+                    ),
+                    _ => Self::prune_expired_entries(
+                        match cursor.take() {
+                            Some(cursor) => Allowances::<T>::iter_from(cursor),
+                            None => Allowances::<T>::iter(),
+                        },
+                        remaining_weight,
+                        &mut used,
+                        |owner, (id, operator), (_, deadline)| {
+                            if !is_unexpired(&deadline, &now) {
+                                Allowances::<T>::remove(&owner, (id, &operator));
+                                Self::deposit_event(Event::<T>::Approval {
+                                    owner,
+                                    operator,
+                                    id,
+                                    amount: Zero::zero(),
+                                });
+                            }
+                        },
+                    ),
+                };
+                if let Some(cursor) = cursor {
+                    ApprovalExpirySweepCursor::<T>::put((map, cursor));
+                    return used;
+                }
+                if map >= 2 {
+                    return used;
+                }
+                map += 1;
+            }
+        }
+
+        /// Passes the entries of `entries` to `prune`, as many as `remaining_weight` allows on top
+        /// of `used`. Returns the raw key to resume from if the weight ran out before the end of
+        /// the map.
+        fn prune_expired_entries<K1, K2, V>(
+            mut entries: PrefixIterator<(K1, K2, V)>,
+            remaining_weight: Weight,
+            used: &mut Weight,
+            mut prune: impl FnMut(K1, K2, V),
+        ) -> Option<Vec<u8>> {
+            let per_entry = T::DbWeight::get().reads_writes(1, 4);
+            loop {
+                if remaining_weight.any_lt(used.saturating_add(per_entry)) {
+                    return Some(entries.last_raw_key().to_vec());
+                }
+                used.saturating_accrue(per_entry);
+                match entries.next() {
+                    Some((key1, key2, value)) => prune(key1, key2, value),
+                    None => return None,
+                }
+            }
+        }
+
+        /// Removes the approvals for collection `id` among the next `budget` entries of
+        /// `entries`, an iterator over a map keyed by owner and then by collection and operator,
+        /// counting them in `removed`. Returns the raw key to resume from if the budget ran out
+        /// before the end of the map.
+        fn sweep_collection_approvals<V>(
+            id: &T::CollectionId,
+            mut entries: PrefixIterator<(T::AccountId, (T::CollectionId, T::AccountId), V)>,
+            budget: &mut u32,
+            removed: &mut u32,
+            remove: impl Fn(&T::AccountId, &(T::CollectionId, T::AccountId)),
+        ) -> Option<Vec<u8>> {
+            loop {
+                if *budget == 0 {
+                    return Some(entries.last_raw_key().to_vec());
+                }
+                *budget -= 1;
+                match entries.next() {
+                    Some((owner, key, _)) => {
+                        if &key.0 == id {
+                            remove(&owner, &key);
+                            *removed += 1;
+                        }
+                    }
+                    None => return None,
+                }
+            }
+        }
+
+        /// Returns the SCALE encoded `(PERMIT_CONTEXT, genesis_hash, owner, operator, approved,
+        /// nonce, deadline)` that `owner` signs for `permit_approval`. The genesis hash keeps
+        /// permits from being replayed on other chains.
+        pub fn permit_payload(
+            owner: &T::AccountId,
+            operator: &T::AccountId,
+            approved: bool,
+            nonce: u64,
+            deadline: BlockNumberFor<T>,
+        ) -> Vec<u8> {
+            (PERMIT_CONTEXT, Self::genesis_hash(), owner, operator, approved, nonce, deadline)
+                .encode()
+        }
+
+        /// Returns the SCALE encoded `(CLAIM_CONTEXT, genesis_hash, id, claimer, amount, nonce)`
+        /// that the issuer of collection `id` signs for `claim_mint`. The genesis hash keeps
+        /// claims from being replayed on other chains.
+        pub fn claim_payload(
+            id: &T::CollectionId,
+            claimer: &T::AccountId,
+            amount: T::Amount,
+            nonce: u64,
+        ) -> Vec<u8> {
+            (CLAIM_CONTEXT, Self::genesis_hash(), id, claimer, amount, nonce).encode()
+        }
+
+        /// Returns the hash of the genesis block, which tells chains apart in signed payloads.
+        fn genesis_hash() -> T::Hash {
+            frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero())
+        }
+
+        /// Returns the raw balance of `account` for collection `id` along with the number of
+        /// decimals it should be displayed with.
+        pub fn display_balance(id: &T::CollectionId, account: &T::AccountId) -> (T::Amount, u8) {
+            (Self::balance_of(account, id), CollectionScale::<T>::get(id))
+        }
+
+        /// Ensures that `operator` can move `amounts` of `from`'s tokens of collections `ids`,
+        /// either through an approval for all of them, as a sub-operator or, for each collection,
+        /// through an approval for the collection, a one-shot approval or an allowance. Approvals
+        /// for all are deducted from the approval cap of the operator, if any, sub-operators from
+        /// their own cap and from the approval cap of the operator that started the delegation, if
+        /// any, allowances from themselves, and one-shot approvals are consumed. Expired approvals
+        /// are ignored, failing with `ApprovalExpired` if nothing else authorizes the transfer.
+        /// Accounts can always move their own tokens.
+        /// Sub-operators whose cap does not cover the transfer fall back on the other approvals,
+        /// failing with `ApprovalCapExceeded` if none authorizes it.
+        pub(crate) fn authorize_operator(
+            from: &T::AccountId,
+            operator: &T::AccountId,
+            ids: &[T::CollectionId],
+            amounts: &[T::Amount],
+        ) -> DispatchResult {
+            if from == operator {
+                return Ok(());
+            }
+            let now = frame_system::Pallet::<T>::block_number();
+            let record = OperatorApprovals::<T>::get(from, operator);
+            if !record.is_some_and(|record| record.is_active(now)) {
+                let mut exhausted = false;
+                if let Some((delegation, root, root_cap)) =
+                    Self::active_sub_delegation(from, operator)
+                {
+                    let remaining = match root_cap {
+                        Some(root_cap) => root_cap.min(delegation.cap),
+                        None => delegation.cap,
+                    };
+                    let total = amounts
+                        .iter()
+                        .try_fold(T::Amount::zero(), |total, amount| total.checked_add(amount));
+                    match total {
+                        Some(total) if total <= remaining => {
+                            if let Some(root_cap) = root_cap {
+                                ApprovalCaps::<T>::insert(from, &root, root_cap - total);
+                            }
+                            SubOperators::<T>::insert(
+                                from,
+                                operator,
+                                SubDelegation {
+                                    cap: delegation.cap - total,
+                                    ..delegation
+                                },
+                            );
+                            return Ok(());
+                        }
+                        _ => exhausted = true,
+                    }
+                }
+                let mut expired = record.is_some_and(|record| record.global);
+                let mut spent: Vec<(T::CollectionId, T::Amount)> = Vec::new();
+                for (id, amount) in ids.iter().zip(amounts) {
+                    match CollectionApprovals::<T>::get(from, (id, operator)) {
+                        Some(expires) if is_unexpired(&expires, &now) => continue,
+                        Some(_) => expired = true,
+                        None => {}
+                    }
+                    match spent.iter_mut().find(|(spent_id, _)| spent_id == id) {
+                        Some((_, total)) => {
+                            *total = total
+                                .checked_add(amount)
+                                .ok_or(Error::<T>::AllowanceExceeded)?
+                        }
+                        None => spent.push((*id, *amount)),
+                    }
+                }
+                for (id, amount) in spent {
+                    if let Some(approved) = OneShotApprovals::<T>::get(from, (id, operator)) {
+                        ensure!(amount <= approved, Error::<T>::AllowanceExceeded);
+                        OneShotApprovals::<T>::remove(from, (id, operator));
+                        continue;
+                    }
+                    let (allowance, expires) = match Allowances::<T>::get(from, (id, operator)) {
+                        Some(allowance) => allowance,
+                        None if exhausted => return Err(Error::<T>::ApprovalCapExceeded.into()),
+                        None if expired => return Err(Error::<T>::ApprovalExpired.into()),
+                        None => return Err(Error::<T>::InsufficientApprovalForAll.into()),
+                    };
+                    ensure!(is_unexpired(&expires, &now), Error::<T>::ApprovalExpired);
+                    ensure!(amount <= allowance, Error::<T>::AllowanceExceeded);
+                    let remaining = allowance - amount;
+                    if remaining.is_zero() {
+                        Allowances::<T>::remove(from, (id, operator));
+                    } else {
+                        Allowances::<T>::insert(from, (id, operator), (remaining, expires));
+                    }
+                }
+                return Ok(());
+            }
+            if let Some(cap) = ApprovalCaps::<T>::get(from, operator) {
+                let mut spent = T::Amount::zero();
+                for amount in amounts {
+                    spent = spent
+                        .checked_add(amount)
+                        .ok_or(Error::<T>::ApprovalCapExceeded)?;
+                }
+                ensure!(spent <= cap, Error::<T>::ApprovalCapExceeded);
+                ApprovalCaps::<T>::insert(from, operator, cap - spent);
+            }
+            Ok(())
+        }
+
+        /// Returns the circulating, reserved and burned amounts of collection `id`.
+        ///
+        /// Tokens cannot be reserved yet, so the reserved amount is always zero and the whole
+        /// supply is circulating.
+        pub fn supply_breakdown(id: &T::CollectionId) -> (T::Amount, T::Amount, T::Amount) {
+            let reserved = T::Amount::zero();
+            let circulating = Self::total_supply(*id).saturating_sub(reserved);
+            (circulating, reserved, Burned::<T>::get(id))
+        }
+
+        /// Returns the amount of tokens of collection `id` that `account` can currently move.
+        ///
+        /// Tokens cannot be reserved or time-locked yet, so this is the whole balance unless the
+        /// account cannot move its tokens at all because of a pause or a freeze.
+        pub fn transferable_balance(id: &T::CollectionId, account: &T::AccountId) -> T::Amount {
+            if Self::is_frozen(id, account) {
+                return Zero::zero();
+            }
+            Self::balance_of(account, id)
+        }
+
+        /// Explains whether `operator` can move `amount` of `owner`'s tokens of collection `id`,
+        /// returning the rule that decides it. Meant for tooling: it neither consumes approval
+        /// caps nor checks balances.
+        pub fn explain_authorization(
+            owner: &T::AccountId,
+            operator: &T::AccountId,
+            id: &T::CollectionId,
+            amount: T::Amount,
+        ) -> AuthorizationResult<T::Amount> {
+            if Self::is_frozen(id, owner) {
+                return AuthorizationResult::DeniedFrozen;
+            }
+            if owner == operator {
+                return AuthorizationResult::GrantedAsOwner;
+            }
+            let now = frame_system::Pallet::<T>::block_number();
+            match OperatorApprovals::<T>::get(owner, operator) {
+                Some(record) if record.is_active(now) => {}
+                record => {
+                    let mut exhausted = None;
+                    if let Some((delegation, _, root_cap)) =
+                        Self::active_sub_delegation(owner, operator)
+                    {
+                        let remaining = match root_cap {
+                            Some(root_cap) => root_cap.min(delegation.cap),
+                            None => delegation.cap,
+                        };
+                        if amount <= remaining {
+                            return AuthorizationResult::GrantedBySubOperator { remaining };
+                        }
+                        exhausted = Some(remaining);
+                    }
+                    let mut expired = record.is_some_and(|record| record.global);
+                    match CollectionApprovals::<T>::get(owner, (id, operator)) {
+                        Some(expires) if is_unexpired(&expires, &now) => {
+                            return AuthorizationResult::GrantedByCollection
+                        }
+                        Some(_) => expired = true,
+                        None => {}
+                    }
+                    match OneShotApprovals::<T>::get(owner, (id, operator)) {
+                        Some(approved) if amount <= approved => {
+                            return AuthorizationResult::GrantedOnce { amount: approved }
+                        }
+                        Some(approved) => {
+                            return AuthorizationResult::DeniedInsufficientAllowance {
+                                remaining: approved,
+                            }
+                        }
+                        None => {}
+                    }
+                    return match Allowances::<T>::get(owner, (id, operator)) {
+                        Some((_, expires)) if !is_unexpired(&expires, &now) => {
+                            AuthorizationResult::DeniedExpired
+                        }
+                        Some((remaining, _)) if amount <= remaining => {
+                            AuthorizationResult::GrantedByAllowance { remaining }
+                        }
+                        Some((remaining, _)) => {
+                            AuthorizationResult::DeniedInsufficientAllowance { remaining }
+                        }
+                        None => match exhausted {
+                            Some(remaining) => {
+                                AuthorizationResult::DeniedInsufficientAllowance { remaining }
+                            }
+                            None if expired => AuthorizationResult::DeniedExpired,
+                            None => AuthorizationResult::DeniedNotApproved,
+                        },
+                    };
+                }
+            }
+            match ApprovalCaps::<T>::get(owner, operator) {
+                Some(remaining) if amount <= remaining => {
+                    AuthorizationResult::GrantedByCap { remaining }
+                }
+                Some(remaining) => AuthorizationResult::DeniedInsufficientAllowance { remaining },
+                None => AuthorizationResult::GrantedByGlobal,
+            }
+        }
+
+        /// Returns the `n` largest holders of collection `id` sorted by descending balance, ties
+        /// broken by descending account. `n` is capped at `MAX_TOP_HOLDERS`.
+        ///
+        /// Every holder of the collection is visited, so this is meant for off-chain queries only.
+        pub fn top_holders(id: &T::CollectionId, n: u32) -> Vec<(T::AccountId, T::Amount)> {
+            let n = n.min(MAX_TOP_HOLDERS) as usize;
+            if n == 0 {
+                return Vec::new();
+            }
+            let mut heap = alloc::collections::BinaryHeap::with_capacity(n + 1);
+            for (account, amount) in Balances::<T>::iter_prefix(id) {
+                heap.push(core::cmp::Reverse((amount, account)));
+                if heap.len() > n {
+                    heap.pop();
+                }
+            }
+            heap.into_sorted_vec()
+                .into_iter()
+                .map(|core::cmp::Reverse((amount, account))| (account, amount))
+                .collect()
+        }
+
+        /// Returns the balances of `account` in the collections owned by `creator`.
+        ///
+        /// Every collection is visited, so this is meant for off-chain queries only.
+        pub fn balances_of_account_by_creator(
+            account: &T::AccountId,
+            creator: &T::AccountId,
+        ) -> Vec<(T::CollectionId, T::Amount)> {
+            CollectionsV2::<T>::iter()
+                .filter(|(_, details)| &details.owner == creator)
+                .filter_map(|(id, _)| Balances::<T>::get(id, account).map(|amount| (id, amount)))
+                .collect()
+        }
+
+        /// Mints the tokens of up to `MaxInflationsPerBlock` inflation schedules, resuming after
+        /// the last schedule minted in the previous block. Schedules of collections that reached
+        /// their maximum supply are removed.
+        pub(crate) fn process_inflation() -> Weight {
+            let limit = T::MaxInflationsPerBlock::get();
+            let mut schedules = match InflationCursor::<T>::take() {
+                Some(cursor) => InflationSchedule::<T>::iter_from(cursor),
+                None => InflationSchedule::<T>::iter(),
+            };
+            let mut processed = 0;
+            while processed < limit {
+                let (id, (per_block, beneficiary)) = match schedules.next() {
+                    Some(schedule) => schedule,
+                    None => break,
+                };
+                processed += 1;
+                let details = match CollectionsV2::<T>::get(id) {
+                    Some(details) => details,
+                    None => continue,
+                };
+                let amount = match details.max_supply {
+                    Some(max) => per_block.min(max.saturating_sub(details.total_supply)),
+                    None => per_block,
+                };
+                if amount.is_zero() {
+                    InflationSchedule::<T>::remove(id);
+                    continue;
+                }
+                // A failed mint, e.g. to a frozen or disallowed beneficiary, is retried next block.
+                let _ = frame_support::storage::with_storage_layer(|| {
+                    Self::update(details.owner, None, Some(beneficiary), vec![id], vec![amount])
+                });
+            }
+            // Only resume from here if the limit was hit before the end of the schedules.
+            if processed == limit {
+                let cursor = schedules.last_raw_key().to_vec();
+                if schedules.next().is_some() {
+                    InflationCursor::<T>::put(cursor);
+                }
+            }
+            T::WeightInfo::process_inflation(processed)
+        }
+
+        /// Pauses or resumes collection `id` on behalf of its freezer or root.
+        fn do_set_collection_paused(
+            origin: OriginFor<T>,
+            id: T::CollectionId,
+            paused: bool,
+        ) -> DispatchResult {
+            match ensure_signed_or_root(origin)? {
+                Some(sender) => Self::ensure_role(&id, &sender, CollectionRole::Freezer)?,
+                None => {
+                    ensure!(
+                        CollectionsV2::<T>::contains_key(id),
+                        Error::<T>::CollectionDoesNotExist
+                    )
+                }
+            }
+            if paused {
+                CollectionPaused::<T>::insert(id, true);
+            } else {
+                CollectionPaused::<T>::remove(id);
+            }
+            Self::deposit_event(Event::<T>::CollectionPauseSet { id, paused });
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl<T: Config> Pallet<T>
+    where
+        T::CollectionId: Ord,
+    {
+        /// Folds `event` into `state`, a map of `(id, account)` to balance.
+        ///
+        /// This is the reference reducer for off-chain indexers and light clients that derive
+        /// balances from the events of the pallet instead of reading `Balances`. Applying every
+        /// event of the pallet in order, starting from an empty map, yields the same non-zero
+        /// balances as on-chain storage. Zero balances are removed, like on-chain.
+        ///
+        /// Pages of an unfinished `force_clear_balances` or `destroy_accounts` do not emit transfer
+        /// events, so the balances of those collections only match storage again once
+        /// `CollectionCleared` or `CollectionDestroyed` is emitted.
+        pub fn apply_event_to_balances(
+            state: &mut alloc::collections::BTreeMap<(T::CollectionId, T::AccountId), T::Amount>,
+            event: &Event<T>,
+        ) {
+            let mut apply = |from: &Option<T::AccountId>,
+                             to: &Option<T::AccountId>,
+                             id: &T::CollectionId,
+                             value: &T::Amount| {
+                if let Some(from) = from {
+                    let balance = state.entry((*id, from.clone())).or_default();
+                    *balance = balance.saturating_sub(*value);
+                }
+                if let Some(to) = to {
+                    let balance = state.entry((*id, to.clone())).or_default();
+                    *balance = balance.saturating_add(*value);
+                }
+            };
+            match event {
+                Event::TransferSingle {
+                    from,
+                    to,
+                    id,
+                    value,
+                    ..
+                } => apply(from, to, id, value),
+                Event::TransferBatch {
+                    from,
+                    to,
+                    ids,
+                    values,
+                    ..
+                } => {
+                    for (id, value) in ids.iter().zip(values.iter()) {
+                        apply(from, to, id, value);
+                    }
+                }
+                Event::MintDistributed { id, recipients, .. } => {
+                    for (to, value) in recipients.iter() {
+                        apply(&None, &Some(to.clone()), id, value);
+                    }
+                }
+                Event::BalanceForceSet {
+                    id, account, new, ..
+                } => {
+                    state.insert((*id, account.clone()), *new);
+                }
+                Event::CollectionCleared { id } | Event::CollectionDestroyed { id } => {
+                    state.retain(|(cleared, _), _| cleared != id)
+                }
+                _ => {}
+            }
+            state.retain(|_, balance| !balance.is_zero());
+        }
+    }
+
+    impl<T: Config> OnKilledAccount<T::AccountId> for Pallet<T> {
+        /// Queues the approvals granted and received by `who` for removal, and the collections
+        /// it owns for hand over, in `on_idle`.
+        fn on_killed_account(who: &T::AccountId) {
+            if OwnerCollectionCount::<T>::get(who) > 0 {
+                ReapedOwners::<T>::insert(who, ());
+            }
+            if OperatorApprovals::<T>::iter_prefix(who).next().is_some()
+                || ApprovalsByOperator::<T>::iter_prefix(who).next().is_some()
+                || CollectionApprovals::<T>::iter_prefix(who).next().is_some()
+                || Allowances::<T>::iter_prefix(who).next().is_some()
+                || OneShotApprovals::<T>::iter_prefix(who).next().is_some()
+                || SubOperators::<T>::iter_prefix(who).next().is_some()
+            {
+                ReapedAccounts::<T>::insert(who, ());
+            }
         }
     }
 }
@@ -0,0 +1,172 @@
+//! Storage migrations for the multitoken pallet.
+
+use super::*;
+use frame_support::{
+    pallet_prelude::*,
+    storage::StoragePrefixedMap,
+    traits::{GetStorageVersion, StorageVersion},
+};
+
+pub mod v1 {
+    use super::*;
+
+    /// Turns every `bool` stored in `OperatorApprovals` into an `ApprovalRecord`, dropping the
+    /// `false` entries since a missing record already means no approval.
+    pub fn migrate<T: Config>() -> Weight {
+        if Pallet::<T>::on_chain_storage_version() >= 1 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let mut translated = 0u64;
+        OperatorApprovals::<T>::translate::<bool, _>(|_, _, approved| {
+            translated += 1;
+            approved.then_some(ApprovalRecord {
+                global: true,
+                expires: None,
+            })
+        });
+        StorageVersion::new(1).put::<Pallet<T>>();
+        T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+    }
+}
+
+pub mod v2 {
+    use super::*;
+    use sp_runtime::traits::Zero;
+
+    /// Removes the zero-valued `Balances` entries left behind by versions that did not prune
+    /// them. Runs from `on_idle`, visiting as many entries as `remaining_weight` allows and
+    /// resuming from `ZeroBalanceSweepCursor` on the next block. Once every entry has been
+    /// visited the on-chain storage version is set to 2, which retires the sweep. It also runs
+    /// at later versions while a cursor is left, see `v3::migrate`.
+    pub fn sweep_zero_balances<T: Config>(remaining_weight: Weight) -> Weight {
+        let db_weight = T::DbWeight::get();
+        let per_entry = db_weight.reads_writes(1, 1);
+        let mut used = db_weight.reads_writes(2, 2);
+        let on_chain = Pallet::<T>::on_chain_storage_version();
+        let cursor = ZeroBalanceSweepCursor::<T>::get();
+        if (on_chain != 1 && cursor.is_none())
+            || remaining_weight.any_lt(used.saturating_add(per_entry))
+        {
+            return db_weight.reads(2);
+        }
+
+        let mut entries = match cursor {
+            Some(cursor) => Balances::<T>::iter_from(cursor),
+            None => Balances::<T>::iter(),
+        };
+        loop {
+            if remaining_weight.any_lt(used.saturating_add(per_entry)) {
+                ZeroBalanceSweepCursor::<T>::put(entries.last_raw_key().to_vec());
+                return used;
+            }
+            used.saturating_accrue(per_entry);
+            match entries.next() {
+                Some((id, account, amount)) => {
+                    if amount.is_zero() {
+                        Balances::<T>::remove(id, account);
+                    }
+                }
+                None => break,
+            }
+        }
+        ZeroBalanceSweepCursor::<T>::kill();
+        if on_chain == 1 {
+            StorageVersion::new(2).put::<Pallet<T>>();
+        }
+        used
+    }
+}
+
+pub mod v3 {
+    use super::*;
+    use frame_support::storage_alias;
+    use sp_runtime::traits::{Saturating, Zero};
+
+    #[storage_alias]
+    pub(crate) type Collections<T: Config> = CountedStorageMap<
+        Pallet<T>,
+        Twox64Concat,
+        <T as Config>::CollectionId,
+        <T as frame_system::Config>::AccountId,
+    >;
+
+    #[storage_alias]
+    pub(crate) type TotalSupply<T: Config> =
+        StorageMap<Pallet<T>, Twox64Concat, <T as Config>::CollectionId, <T as Config>::Amount>;
+
+    #[storage_alias]
+    pub(crate) type Fungible<T: Config> =
+        StorageMap<Pallet<T>, Twox64Concat, <T as Config>::CollectionId, bool>;
+
+    /// Moves the owner, supply and fungibility of every collection from `Collections`,
+    /// `TotalSupply` and `Fungible` into a single `CollectionDetails` in `CollectionsV2`, and
+    /// counts the collections of every owner into `OwnerCollectionCount`. Collections created
+    /// before `Fungible` existed have no entry in it and stay fungible, as they always were.
+    /// Likewise, the supply of collections created before `TotalSupply` existed is rebuilt from
+    /// the balances of their holders.
+    ///
+    /// If the zero balance sweep of v2 has not finished yet, it is resumed from the start of
+    /// `Balances` through `ZeroBalanceSweepCursor`, so it keeps running under version 3.
+    pub fn migrate<T: Config>() -> Weight {
+        let on_chain = Pallet::<T>::on_chain_storage_version();
+        if on_chain < 1 || on_chain >= 3 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let mut migrated = 0u64;
+        let mut holders = 0u64;
+        for (id, owner) in Collections::<T>::drain() {
+            OwnerCollectionCount::<T>::mutate(&owner, |count| *count = count.saturating_add(1));
+            let total_supply = match TotalSupply::<T>::take(id) {
+                Some(total_supply) => total_supply,
+                None => Balances::<T>::iter_prefix_values(id).fold(
+                    T::Amount::zero(),
+                    |total, amount| {
+                        holders += 1;
+                        total.saturating_add(amount)
+                    },
+                ),
+            };
+            let details = CollectionDetails {
+                owner,
+                total_supply,
+                max_supply: None,
+                frozen: false,
+                fungible: Fungible::<T>::take(id).unwrap_or(true),
+            };
+            CollectionsV2::<T>::insert(id, details);
+            migrated += 1;
+        }
+        // Supplies and flags of collections removed by older versions without cleaning them up.
+        let _ = TotalSupply::<T>::clear(u32::MAX, None);
+        let _ = Fungible::<T>::clear(u32::MAX, None);
+
+        let mut writes = 1;
+        if on_chain == 1 && !ZeroBalanceSweepCursor::<T>::exists() {
+            ZeroBalanceSweepCursor::<T>::put(Balances::<T>::final_prefix().to_vec());
+            writes += 1;
+        }
+        StorageVersion::new(3).put::<Pallet<T>>();
+        T::DbWeight::get().reads_writes(4 * migrated + holders + 2, 5 * migrated + writes)
+    }
+}
+
+pub mod v4 {
+    use super::*;
+
+    /// Fills `ApprovalsByOperator` from the existing `OperatorApprovals`.
+    pub fn migrate<T: Config>() -> Weight {
+        if Pallet::<T>::on_chain_storage_version() != 3 {
+            return T::DbWeight::get().reads(1);
+        }
+
+        let mut indexed = 0u64;
+        for (owner, operator, _) in OperatorApprovals::<T>::iter() {
+            ApprovalsByOperator::<T>::insert(&operator, &owner, ());
+            indexed += 1;
+        }
+        StorageVersion::new(4).put::<Pallet<T>>();
+        T::DbWeight::get().reads_writes(indexed + 1, indexed + 1)
+    }
+}
@@ -1,13 +1,17 @@
+use frame_support::parameter_types;
 use frame_support::sp_io;
-use frame_support::traits::{ConstU16, ConstU64};
+use frame_support::traits::{ConstU16, ConstU32, ConstU64, Contains, Hooks};
+use frame_support::weights::constants::RocksDbWeight;
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::{
-    testing::Header,
+    testing::{Header, TestSignature, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
+    DispatchError, DispatchResult,
 };
 
 use crate as pallet_multitoken;
-use crate::Next;
+use crate::UriPolicy;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -39,28 +43,112 @@ impl frame_system::Config for Test {
     type Header = Header;
     type RuntimeEvent = RuntimeEvent;
     type BlockHashCount = ConstU64<250>;
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type Version = ();
     type PalletInfo = PalletInfo;
     type AccountData = ();
     type OnNewAccount = ();
-    type OnKilledAccount = ();
+    type OnKilledAccount = Multitoken;
     type SystemWeightInfo = ();
     type SS58Prefix = ConstU16<42>;
     type OnSetCode = ();
-    type MaxConsumers = frame_support::traits::ConstU32<16>;
+    type MaxConsumers = ConstU32<16>;
 }
 
-impl Next for u64 {
-    fn next(&self) -> Self {
-        self.saturating_add(1)
-    }
+parameter_types! {
+    pub static MaxCreatesPerBlock: u32 = 10;
+    pub static MaxMintPerCall: Option<u64> = None;
+    pub static EmitHolderExitEvents: bool = false;
+    pub static CustomCollectionIds: bool = false;
+    pub static NullAccount: Option<u64> = None;
+    pub static UriTooLongPolicy: UriPolicy = UriPolicy::Reject;
+    pub static TransferEventTopics: bool = true;
+    pub static MaxInflationsPerBlock: u32 = 10;
+    pub static DeniedCreator: Option<u64> = None;
+    pub static DeniedOperator: Option<u64> = None;
+    pub static KeepTransferHistory: bool = false;
+    pub static RequireMintConsent: bool = false;
+    pub static EmitOperatorEvents: bool = false;
 }
 
 impl pallet_multitoken::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type CollectionId = u64;
     type Amount = u64;
+    type MaxBatchSize = ConstU32<10>;
+    type MaxCreatesPerBlock = MaxCreatesPerBlock;
+    type WeightInfo = ();
+    type MaxMintPerCall = MaxMintPerCall;
+    type EmitHolderExitEvents = EmitHolderExitEvents;
+    type CollectionHooks = MockCollectionHooks;
+    type MaxUriLength = ConstU32<16>;
+    type StringLimit = ConstU32<16>;
+    type BridgeOrigin = EnsureRoot<u64>;
+    type CustomCollectionIds = CustomCollectionIds;
+    type NullAccount = NullAccount;
+    type UriTooLongPolicy = UriTooLongPolicy;
+    type Treasury = ConstU64<99>;
+    type TransferEventTopics = TransferEventTopics;
+    type MaxInflationsPerBlock = MaxInflationsPerBlock;
+    type CreateFilter = MockCreateFilter;
+    type MaxMemoLength = ConstU32<32>;
+    type KeepTransferHistory = KeepTransferHistory;
+    type TransferHistoryLen = ConstU32<3>;
+    type RequireMintConsent = RequireMintConsent;
+    type EmitOperatorEvents = EmitOperatorEvents;
+    type MaxApprovalsCleared = ConstU32<4>;
+    type MaxDelegationDepth = ConstU32<2>;
+    type OperatorFilter = MockOperatorFilter;
+    type OffchainSignature = TestSignature;
+    type OffchainPublic = UintAuthorityId;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = MultitokenBenchmarkHelper;
+}
+
+/// Hook 0 rejects every transfer, hook 1 accepts every transfer.
+pub struct MockCollectionHooks;
+
+impl pallet_multitoken::CollectionHooks<u64, u64, u64> for MockCollectionHooks {
+    fn on_transfer(
+        hook: u8,
+        _id: &u64,
+        _from: Option<&u64>,
+        _to: Option<&u64>,
+        _amount: &u64,
+    ) -> DispatchResult {
+        match hook {
+            1 => Ok(()),
+            _ => Err(DispatchError::Other("transfer rejected by collection hook")),
+        }
+    }
+}
+
+/// Lets everyone but `DeniedCreator` create collections.
+pub struct MockCreateFilter;
+
+impl Contains<u64> for MockCreateFilter {
+    fn contains(who: &u64) -> bool {
+        DeniedCreator::get() != Some(*who)
+    }
+}
+
+/// Lets every account but `DeniedOperator` be approved as an operator.
+pub struct MockOperatorFilter;
+
+impl Contains<u64> for MockOperatorFilter {
+    fn contains(who: &u64) -> bool {
+        DeniedOperator::get() != Some(*who)
+    }
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MultitokenBenchmarkHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_multitoken::BenchmarkHelper<u64, TestSignature> for MultitokenBenchmarkHelper {
+    fn sign(payload: &[u8]) -> (u64, TestSignature) {
+        (1, TestSignature(1, payload.to_vec()))
+    }
 }
 
 // Build genesis storage according to the mock runtime.
@@ -70,3 +158,11 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
         .unwrap()
         .into()
 }
+
+/// Advances the chain up to block `n`, running the pallet's `on_initialize` on every new block.
+pub fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        System::set_block_number(System::block_number() + 1);
+        Multitoken::on_initialize(System::block_number());
+    }
+}
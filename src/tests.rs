@@ -1,6 +1,89 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+    migrations::v3, mock::*, Allowances, ApprovalExpirySweepCursor, ApprovalRecord,
+    ApprovalsByOperator, AuthorizationResult, Balances, BatchOp, Claimable, CollectionApprovals,
+    CollectionDestroying, CollectionDetails, CollectionPaused, CollectionsV2, DestroyStage,
+    DestroyWitness, Error, Event, FrozenAccounts, Next, NextCollectionId, OneShotApprovals,
+    OperatorApprovals, OrphanSweepCursor, OwnerCollectionCount, ReapedAccounts, ReapedOwners,
+    RecipientAllowlist, RoyaltyInfo, TransferTax, UriPolicy, ZeroBalanceSweepCursor, LOG_TARGET,
+};
+use codec::{Encode, MaxEncodedLen};
+use frame_support::{
+    assert_noop, assert_ok, assert_storage_noop,
+    pallet_prelude::{BoundedVec, Weight},
+    storage::unhashed,
+    traits::{Get, GetStorageVersion, Hooks, StorageVersion},
+};
 use frame_system::ensure_signed;
+use sp_runtime::{testing::TestSignature, ArithmeticError, DispatchError};
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, Once},
+};
+
+/// Replays every event emitted so far through `apply_event_to_balances` and asserts that the
+/// reconstructed balances match `Balances`.
+fn assert_events_match_balances() {
+    let mut state = BTreeMap::new();
+    for record in System::events() {
+        if let RuntimeEvent::Multitoken(event) = record.event {
+            Multitoken::apply_event_to_balances(&mut state, &event);
+        }
+    }
+    let on_chain: BTreeMap<_, _> = Balances::<Test>::iter()
+        .map(|(id, account, amount)| ((id, account), amount))
+        .collect();
+    assert_eq!(state, on_chain);
+}
+
+/// Removes everything left of collection `id`, which must already be destroying.
+fn complete_destroy(id: u64) {
+    let witness = DestroyWitness {
+        accounts: u32::MAX,
+        approvals: u32::MAX,
+    };
+    assert_ok!(Multitoken::destroy_accounts(RuntimeOrigin::signed(1), id, witness));
+    assert_ok!(Multitoken::finish_destroy(RuntimeOrigin::signed(1), id));
+}
+
+/// Logger keeping the messages logged by each test thread, so that tests can assert on them.
+struct CaptureLogger;
+
+static CAPTURED_LOGS: Mutex<Vec<(std::thread::ThreadId, String)>> = Mutex::new(Vec::new());
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target() == LOG_TARGET
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            let message = format!("{}", record.args());
+            CAPTURED_LOGS
+                .lock()
+                .unwrap()
+                .push((std::thread::current().id(), message));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Returns the messages logged by the pallet so far in the current test.
+fn captured_logs() -> Vec<String> {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        log::set_logger(&CaptureLogger).unwrap();
+        log::set_max_level(log::LevelFilter::Debug);
+    });
+    let current = std::thread::current().id();
+    CAPTURED_LOGS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(thread, _)| *thread == current)
+        .map(|(_, message)| message.clone())
+        .collect()
+}
 
 #[test]
 fn test_creating_a_collection_should_work() {
@@ -10,7 +93,7 @@ fn test_creating_a_collection_should_work() {
         let owner_account = ensure_signed(RuntimeOrigin::signed(1)).unwrap();
         assert_eq!(Multitoken::next_collection_id(), 0);
         assert_eq!(Multitoken::collections(0), None);
-        assert_ok!(Multitoken::create(owner.clone()));
+        assert_ok!(Multitoken::create(owner.clone(), true));
         System::assert_last_event(Event::CollectionCreated { owner: owner_account, id: 0 }.into());
         assert_eq!(Multitoken::next_collection_id(), 1);
         assert_eq!(Multitoken::collections(0), Some(ensure_signed(owner).unwrap()));
@@ -26,7 +109,7 @@ fn test_only_owner_can_mint() {
         let receiver = RuntimeOrigin::signed(2);
         let receiver_account = ensure_signed(receiver.clone()).unwrap();
         assert_eq!(Multitoken::balance_of(&receiver_account, &0), 0);
-        assert_ok!(Multitoken::create(owner.clone()));
+        assert_ok!(Multitoken::create(owner.clone(), true));
         assert_eq!(Multitoken::balance_of(&receiver_account, &0), 0);
 
         assert_noop!(Multitoken::mint(receiver, receiver_account, 0, 100), Error::<Test>::InvalidOwner);
@@ -37,7 +120,4163 @@ fn test_only_owner_can_mint() {
             to: Some(receiver_account),
             id: 0,
             value: 100,
+            fungible: true,
         }.into());
         assert_eq!(Multitoken::balance_of(&receiver_account.clone(), &0), 100);
     });
-}
\ No newline at end of file
+}
+#[test]
+fn test_batch_with_mismatched_lengths_should_fail() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_noop!(
+            Multitoken::burn_batch(owner, vec![0, 0], vec![1]),
+            Error::<Test>::MismatchedArrayLength
+        );
+    });
+}
+
+#[test]
+fn test_empty_batch_should_fail() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_noop!(Multitoken::burn_batch(owner, vec![], vec![]), Error::<Test>::EmptyBatch);
+    });
+}
+
+#[test]
+fn test_batch_over_max_size_should_fail() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        let max = <Test as crate::Config>::MaxBatchSize::get() as usize;
+        assert_noop!(
+            Multitoken::burn_batch(owner, vec![0; max + 1], vec![1; max + 1]),
+            Error::<Test>::BatchTooLarge
+        );
+    });
+}
+
+#[test]
+fn test_is_collection_owner() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert!(Multitoken::is_collection_owner(&0, &1));
+        assert!(!Multitoken::is_collection_owner(&0, &2));
+        assert!(!Multitoken::is_collection_owner(&1, &1));
+    });
+}
+
+#[test]
+fn test_collections_up_to_is_bounded_prefix() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for _ in 0..5 {
+            assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        }
+        let all = Multitoken::all_collections();
+        assert_eq!(all.len(), 5);
+        for limit in 0..7 {
+            let bounded = Multitoken::collections_up_to(limit);
+            assert!(bounded.len() <= limit as usize);
+            assert_eq!(bounded.len(), all.len().min(limit as usize));
+            assert_eq!(bounded[..], all[..bounded.len()]);
+        }
+    });
+}
+
+#[test]
+fn test_are_approved_for_all_preserves_order() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::set_approval_for_all(owner.clone(), 2, true, None));
+        assert_ok!(Multitoken::set_approval_for_all(owner.clone(), 4, true, None));
+        assert_ok!(Multitoken::set_approval_for_all(owner, 4, false, None));
+        assert_ok!(Multitoken::set_approval_for_all(RuntimeOrigin::signed(3), 5, true, None));
+        assert_eq!(
+            Multitoken::are_approved_for_all(&1, &[2, 3, 4, 5, 2]),
+            vec![true, false, false, false, true]
+        );
+        assert_eq!(Multitoken::are_approved_for_all(&1, &[]), Vec::<bool>::new());
+    });
+}
+
+#[test]
+fn test_create_collection_returns_sequential_ids() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_eq!(Multitoken::create_collection(1, true), Ok(0));
+        assert_eq!(Multitoken::create_collection(2, true), Ok(1));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(3), true));
+        System::assert_last_event(Event::CollectionCreated { id: 2, owner: 3 }.into());
+        assert_eq!(Multitoken::create_collection(1, true), Ok(3));
+        assert_eq!(Multitoken::collections(2), Some(3));
+        assert_eq!(Multitoken::next_collection_id(), 4);
+    });
+}
+
+#[test]
+fn test_creates_are_rate_limited_per_block() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        MaxCreatesPerBlock::set(2);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_noop!(Multitoken::create(owner.clone(), true), Error::<Test>::CreateRateLimited);
+
+        run_to_block(2);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_noop!(Multitoken::create(owner, true), Error::<Test>::CreateRateLimited);
+        assert_eq!(Multitoken::next_collection_id(), 4);
+    });
+}
+
+#[test]
+fn test_merge_collections_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 10));
+        assert_ok!(Multitoken::mint(owner.clone(), 3, 0, 20));
+        assert_ok!(Multitoken::mint(owner.clone(), 4, 0, 30));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 1, 5));
+
+        assert_noop!(
+            Multitoken::merge_collections(RuntimeOrigin::signed(2), 0, 1, 10),
+            Error::<Test>::InvalidOwner
+        );
+        assert_noop!(
+            Multitoken::merge_collections(owner.clone(), 0, 0, 10),
+            Error::<Test>::CannotMergeIntoItself
+        );
+        assert_ok!(Multitoken::pause_collection(owner.clone(), 1));
+        assert_noop!(
+            Multitoken::merge_collections(owner.clone(), 0, 1, 10),
+            Error::<Test>::CollectionPaused
+        );
+        assert_ok!(Multitoken::unpause_collection(owner.clone(), 1));
+
+        assert_ok!(Multitoken::merge_collections(owner.clone(), 0, 1, 2));
+        assert_eq!(Multitoken::collections(0), Some(1));
+        System::assert_last_event(
+            Event::BatchProgress {
+                operation: BatchOp::MergeCollections,
+                id: 0,
+                processed: 2,
+                remaining: 1,
+            }
+            .into(),
+        );
+        assert_eq!(Multitoken::total_supply(0) + Multitoken::total_supply(1), 65);
+        assert_ok!(Multitoken::merge_collections(owner, 0, 1, 2));
+        System::assert_last_event(
+            Event::CollectionsMerged {
+                source: 0,
+                target: 1,
+            }
+            .into(),
+        );
+        assert_eq!(Multitoken::total_supply(0), 0);
+        assert_eq!(CollectionDestroying::<Test>::get(0), Some(DestroyStage::Accounts));
+        complete_destroy(0);
+
+        assert_eq!(Multitoken::collections(0), None);
+        assert_eq!(Multitoken::total_supply(1), 65);
+        assert_eq!(Multitoken::balance_of(&2, &1), 15);
+        assert_eq!(Multitoken::balance_of(&3, &1), 20);
+        assert_eq!(Multitoken::balance_of(&4, &1), 30);
+        for account in 2..=4 {
+            assert_eq!(Multitoken::balance_of(&account, &0), 0);
+        }
+        // Every move was reported as a burn of the source and a mint of the target.
+        assert_events_match_balances();
+    });
+}
+
+#[test]
+fn test_batch_transfer_reports_failing_index() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        let ids = vec![0, 1, 2];
+        for id in ids.iter() {
+            assert_ok!(Multitoken::create(owner.clone(), true));
+            assert_ok!(Multitoken::mint(owner.clone(), 1, *id, 10));
+        }
+        assert_noop!(
+            Multitoken::safe_batch_transfer_from(owner, 1, 2, ids.clone(), vec![5, 50, 5]),
+            Error::<Test>::InsufficientBalanceInBatch { index: 1 }
+        );
+        // The reported index points at the only id whose balance is too low.
+        assert_eq!(Multitoken::balance_of(&1, &ids[1]), 10);
+        assert!(Multitoken::balance_of(&1, &ids[0]) >= 5);
+        assert!(Multitoken::balance_of(&1, &ids[2]) >= 5);
+    });
+}
+
+#[test]
+fn test_non_fungible_collection_rejects_second_mint() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), false));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert!(!Multitoken::fungible(0));
+        assert!(Multitoken::fungible(1));
+
+        assert_noop!(Multitoken::mint(owner.clone(), 2, 0, 2), Error::<Test>::NotFungible);
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 1));
+        assert_noop!(Multitoken::mint(owner.clone(), 3, 0, 1), Error::<Test>::NotFungible);
+        assert_eq!(Multitoken::total_supply(0), 1);
+
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 1, 100));
+        assert_ok!(Multitoken::mint(owner, 3, 1, 100));
+        assert_eq!(Multitoken::total_supply(1), 200);
+    });
+}
+
+#[test]
+fn test_transfer_batch_weight_scales_linearly() {
+    new_test_ext().execute_with(|| {
+        let weights: Vec<_> = (1..=4).map(Multitoken::transfer_batch_weight).collect();
+        let step = weights[1] - weights[0];
+        assert!(step.ref_time() > 0);
+        for pair in weights.windows(2) {
+            assert_eq!(pair[1] - pair[0], step);
+        }
+        assert_eq!(
+            Multitoken::transfer_batch_weight(3),
+            <() as crate::WeightInfo>::safe_batch_transfer_from(3)
+        );
+    });
+}
+
+#[test]
+fn test_approval_record_drives_authorization() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 1, 0, 100));
+        assert_noop!(
+            Multitoken::safe_transfer_from(RuntimeOrigin::signed(2), 1, 3, 0, 10),
+            Error::<Test>::InsufficientApprovalForAll
+        );
+
+        assert_ok!(Multitoken::set_approval_for_all(owner.clone(), 2, true, None));
+        assert_eq!(
+            Multitoken::operator_approvals(1, 2),
+            Some(ApprovalRecord {
+                global: true,
+                expires: None
+            })
+        );
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(2), 1, 3, 0, 10));
+
+        OperatorApprovals::<Test>::insert(
+            1,
+            2,
+            ApprovalRecord {
+                global: true,
+                expires: Some(1),
+            },
+        );
+        assert_ok!(Multitoken::safe_batch_transfer_from(
+            RuntimeOrigin::signed(2),
+            1,
+            3,
+            vec![0],
+            vec![10]
+        ));
+        System::set_block_number(2);
+        assert!(!Multitoken::is_approved_for_all(&1, &2));
+        assert_noop!(
+            Multitoken::safe_transfer_from(RuntimeOrigin::signed(2), 1, 3, 0, 10),
+            Error::<Test>::ApprovalExpired
+        );
+
+        assert_ok!(Multitoken::set_approval_for_all(owner, 2, false, None));
+        assert_eq!(Multitoken::operator_approvals(1, 2), None);
+        assert_eq!(Multitoken::balance_of(&3, &0), 20);
+    });
+}
+
+#[test]
+fn test_approval_migration_preserves_existing_approvals() {
+    new_test_ext().execute_with(|| {
+        unhashed::put(&OperatorApprovals::<Test>::hashed_key_for(1, 2), &true);
+        unhashed::put(&OperatorApprovals::<Test>::hashed_key_for(1, 3), &false);
+        unhashed::put(&OperatorApprovals::<Test>::hashed_key_for(4, 1), &true);
+        StorageVersion::new(0).put::<Multitoken>();
+
+        Multitoken::on_runtime_upgrade();
+
+        assert_eq!(Multitoken::on_chain_storage_version(), 4);
+        assert!(Multitoken::is_approved_for_all(&1, &2));
+        assert!(Multitoken::is_approved_for_all(&4, &1));
+        assert!(!Multitoken::is_approved_for_all(&1, &3));
+        assert_eq!(Multitoken::operator_approvals(1, 3), None);
+        assert_eq!(OperatorApprovals::<Test>::iter().count(), 2);
+        assert_eq!(ApprovalsByOperator::<Test>::iter().count(), 2);
+        assert!(ApprovalsByOperator::<Test>::contains_key(2, 1));
+        assert!(ApprovalsByOperator::<Test>::contains_key(1, 4));
+    });
+}
+
+#[test]
+fn test_emit_metadata_update() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 10));
+        assert_noop!(
+            Multitoken::emit_metadata_update(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::InvalidOwner
+        );
+        assert_noop!(
+            Multitoken::emit_metadata_update(owner.clone(), 1),
+            Error::<Test>::CollectionDoesNotExist
+        );
+
+        assert_ok!(Multitoken::emit_metadata_update(owner, 0));
+        System::assert_last_event(Event::MetadataUpdated { id: 0 }.into());
+        // Only the event was deposited; pallet storage is untouched.
+        assert_eq!(Multitoken::collections(0), Some(1));
+        assert_eq!(Multitoken::balance_of(&2, &0), 10);
+        assert_eq!(Multitoken::total_supply(0), 10);
+    });
+}
+
+#[test]
+fn test_claim_and_approve_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        let player = RuntimeOrigin::signed(2);
+        let game_server = 3;
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_noop!(
+            Multitoken::allow_claim(player.clone(), 0, 2, 50),
+            Error::<Test>::InvalidOwner
+        );
+        assert_noop!(
+            Multitoken::claim_and_approve(player.clone(), 0, game_server),
+            Error::<Test>::NothingToClaim
+        );
+
+        assert_ok!(Multitoken::allow_claim(owner, 0, 2, 50));
+        System::assert_last_event(
+            Event::ClaimAllowed {
+                id: 0,
+                account: 2,
+                amount: 50,
+            }
+            .into(),
+        );
+        assert_ok!(Multitoken::claim_and_approve(player.clone(), 0, game_server));
+        System::assert_has_event(
+            Event::TransferSingle {
+                operator: 2,
+                from: None,
+                to: Some(2),
+                id: 0,
+                value: 50,
+                fungible: true,
+            }
+            .into(),
+        );
+        System::assert_last_event(
+            Event::ApprovalForAll {
+                account: 2,
+                operator: game_server,
+                approved: true,
+            }
+            .into(),
+        );
+        assert_eq!(Multitoken::balance_of(&2, &0), 50);
+        assert!(Multitoken::is_approved_for_all(&2, &game_server));
+        assert_eq!(Multitoken::claimable(0, 2), 0);
+
+        assert_noop!(
+            Multitoken::claim_and_approve(player, 0, game_server),
+            Error::<Test>::NothingToClaim
+        );
+    });
+}
+
+#[test]
+fn test_transfer_batch_event_is_bounded() {
+    type BoundedBatch = BoundedVec<u64, <Test as crate::Config>::MaxBatchSize>;
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        let max = <Test as crate::Config>::MaxBatchSize::get() as usize;
+        for _ in 0..max {
+            assert_ok!(Multitoken::create(owner.clone(), true));
+        }
+        let ids: Vec<u64> = (0..max as u64).collect();
+        assert_ok!(Multitoken::mint_batch(owner, 2, ids.clone(), vec![1; max]));
+
+        let event = System::events()
+            .pop()
+            .expect("an event was deposited")
+            .event;
+        let RuntimeEvent::Multitoken(Event::TransferBatch {
+            ids: event_ids,
+            values,
+            ..
+        }) = event
+        else {
+            panic!("expected a TransferBatch event");
+        };
+        assert_eq!(event_ids.len(), max);
+        assert_eq!(event_ids.into_inner(), ids);
+        assert!(values.encoded_size() <= BoundedBatch::max_encoded_len());
+        assert!(BoundedBatch::try_from(vec![0; max + 1]).is_err());
+    });
+}
+
+#[test]
+fn test_mint_and_distribute_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 5));
+        assert_noop!(
+            Multitoken::mint_and_distribute(RuntimeOrigin::signed(2), 0, vec![(3, 10)]),
+            Error::<Test>::InvalidOwner
+        );
+        assert_noop!(
+            Multitoken::mint_and_distribute(owner.clone(), 0, vec![]),
+            Error::<Test>::EmptyBatch
+        );
+
+        let recipients = vec![(2, 10), (3, 20), (4, 30)];
+        assert_ok!(Multitoken::mint_and_distribute(owner, 0, recipients.clone()));
+        System::assert_last_event(
+            Event::MintDistributed {
+                operator: 1,
+                id: 0,
+                recipients: BoundedVec::truncate_from(recipients),
+                fungible: true,
+            }
+            .into(),
+        );
+        assert_eq!(Multitoken::balance_of(&2, &0), 15);
+        assert_eq!(Multitoken::balance_of(&3, &0), 20);
+        assert_eq!(Multitoken::balance_of(&4, &0), 30);
+        assert_eq!(Multitoken::total_supply(0), 65);
+        assert_events_match_balances();
+
+        // Each mint goes through the same checks as `mint`, and a single failing recipient
+        // leaves the others untouched.
+        NullAccount::set(Some(9));
+        assert_noop!(
+            Multitoken::mint_and_distribute(RuntimeOrigin::signed(1), 0, vec![(2, 1), (9, 1)]),
+            Error::<Test>::MintToNullAccount
+        );
+        NullAccount::set(None);
+        assert_ok!(Multitoken::set_max_supply(RuntimeOrigin::signed(1), 0, Some(70)));
+        assert_noop!(
+            Multitoken::mint_and_distribute(RuntimeOrigin::signed(1), 0, vec![(2, 5), (3, 1)]),
+            Error::<Test>::MaxSupplyExceeded
+        );
+        assert_ok!(Multitoken::pause_collection(RuntimeOrigin::signed(1), 0));
+        assert_noop!(
+            Multitoken::mint_and_distribute(RuntimeOrigin::signed(1), 0, vec![(2, 1)]),
+            Error::<Test>::CollectionPaused
+        );
+    });
+}
+
+#[test]
+fn test_recipient_allowlist_should_work() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 1, 0, 100));
+        assert_noop!(
+            Multitoken::set_recipient_allowlist_enforced(RuntimeOrigin::signed(2), 0, true),
+            Error::<Test>::InvalidOwner
+        );
+
+        assert_ok!(Multitoken::set_recipient_allowlist_enforced(owner.clone(), 0, true));
+        System::assert_last_event(
+            Event::RecipientAllowlistToggled {
+                id: 0,
+                enforced: true,
+            }
+            .into(),
+        );
+        assert_ok!(Multitoken::allow_recipient(owner.clone(), 0, 2, true));
+        System::assert_last_event(
+            Event::RecipientAllowed {
+                id: 0,
+                account: 2,
+                allowed: true,
+            }
+            .into(),
+        );
+
+        assert_ok!(Multitoken::safe_transfer_from(owner.clone(), 1, 2, 0, 10));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 10));
+        assert_noop!(
+            Multitoken::safe_transfer_from(owner.clone(), 1, 3, 0, 10),
+            Error::<Test>::RecipientNotAllowed
+        );
+        assert_noop!(Multitoken::mint(owner.clone(), 3, 0, 10), Error::<Test>::RecipientNotAllowed);
+        // Burns are always allowed.
+        assert_ok!(Multitoken::burn(RuntimeOrigin::signed(2), 0, 5));
+
+        assert_ok!(Multitoken::allow_recipient(owner.clone(), 0, 2, false));
+        assert_noop!(
+            Multitoken::safe_transfer_from(owner.clone(), 1, 2, 0, 10),
+            Error::<Test>::RecipientNotAllowed
+        );
+
+        // Switching the allowlist off leaves the freezes of the collection alone.
+        assert_ok!(Multitoken::freeze_account(owner.clone(), 0, 2, true));
+        assert_ok!(Multitoken::set_recipient_allowlist_enforced(owner.clone(), 0, false));
+        assert!(Multitoken::is_frozen(&0, &2));
+        assert_ok!(Multitoken::safe_transfer_from(owner, 1, 3, 0, 10));
+        assert_eq!(Multitoken::balance_of(&2, &0), 15);
+        assert_eq!(Multitoken::balance_of(&3, &0), 10);
+    });
+}
+
+#[test]
+fn test_collection_owner_matches_getter() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(2), false));
+        for id in 0..3 {
+            assert_eq!(Multitoken::collection_owner(&id), Multitoken::collections(id));
+        }
+        assert_eq!(Multitoken::collection_owner(&0), Some(1));
+        assert_eq!(Multitoken::collection_owner(&1), Some(2));
+        assert_eq!(Multitoken::collection_owner(&2), None);
+    });
+}
+
+#[test]
+fn test_mint_batch_with_repeated_ids_updates_supply() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint_batch(
+            owner.clone(),
+            2,
+            vec![0, 1, 0, 0, 1],
+            vec![1, 2, 3, 4, 5]
+        ));
+        assert_eq!(Multitoken::balance_of(&2, &0), 8);
+        assert_eq!(Multitoken::balance_of(&2, &1), 7);
+        assert_eq!(Multitoken::total_supply(0), 8);
+        assert_eq!(Multitoken::total_supply(1), 7);
+
+        assert_ok!(Multitoken::burn_batch(RuntimeOrigin::signed(2), vec![0, 0, 1], vec![2, 3, 7]));
+        assert_eq!(Multitoken::total_supply(0), 3);
+        assert_eq!(Multitoken::total_supply(1), 0);
+        assert_events_match_balances();
+    });
+}
+
+#[test]
+fn test_claim_mint_with_owner_signature() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        let claimer = RuntimeOrigin::signed(2);
+        assert_ok!(Multitoken::create(owner, true));
+        let payload = Multitoken::claim_payload(&0, &2, 50, 0);
+
+        // Signatures over the bare fields are not valid claims.
+        let undomained = TestSignature(1, (0u64, 2u64, 50u64, 0u64).encode());
+        assert_noop!(
+            Multitoken::claim_mint(claimer.clone(), 0, 50, undomained),
+            Error::<Test>::InvalidSignature
+        );
+
+        let forged = TestSignature(3, payload.clone());
+        assert_noop!(
+            Multitoken::claim_mint(claimer.clone(), 0, 50, forged),
+            Error::<Test>::InvalidSignature
+        );
+        let tampered = TestSignature(1, payload.clone());
+        assert_noop!(
+            Multitoken::claim_mint(claimer.clone(), 0, 100, tampered),
+            Error::<Test>::InvalidSignature
+        );
+
+        let signature = TestSignature(1, payload);
+        assert_ok!(Multitoken::claim_mint(claimer.clone(), 0, 50, signature.clone()));
+        System::assert_last_event(
+            Event::TransferSingle {
+                operator: 1,
+                from: None,
+                to: Some(2),
+                id: 0,
+                value: 50,
+                fungible: true,
+            }
+            .into(),
+        );
+        assert_eq!(Multitoken::balance_of(&2, &0), 50);
+        assert_eq!(Multitoken::claim_nonce(2), 1);
+
+        assert_noop!(
+            Multitoken::claim_mint(claimer, 0, 50, signature),
+            Error::<Test>::InvalidSignature
+        );
+    });
+}
+
+#[test]
+fn test_on_idle_sweeps_zero_balances() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(1), 2, 0, 10));
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(1), 3, 0, 20));
+        for account in 4..7 {
+            Balances::<Test>::insert(0, account, 0);
+        }
+        StorageVersion::new(1).put::<Multitoken>();
+
+        let db_weight = <Test as frame_system::Config>::DbWeight::get();
+        let budget = db_weight
+            .reads_writes(2, 2)
+            .saturating_add(db_weight.reads_writes(2, 2));
+        Multitoken::on_idle(1, budget);
+        assert!(ZeroBalanceSweepCursor::<Test>::get().is_some());
+        assert_eq!(Multitoken::on_chain_storage_version(), 1);
+
+        let mut passes = 1;
+        while Multitoken::on_chain_storage_version() == 1 {
+            Multitoken::on_idle(1, budget);
+            passes += 1;
+        }
+        assert!(passes >= 3);
+        assert_eq!(Multitoken::on_chain_storage_version(), 2);
+        assert!(ZeroBalanceSweepCursor::<Test>::get().is_none());
+        assert_eq!(Balances::<Test>::iter().count(), 2);
+        assert_eq!(Multitoken::balance_of(&2, &0), 10);
+        assert_eq!(Multitoken::balance_of(&3, &0), 20);
+
+        // Once retired, the sweep does nothing.
+        Balances::<Test>::insert(0, 4, 0);
+        Multitoken::on_idle(1, budget);
+        assert_eq!(Balances::<Test>::iter().count(), 3);
+    });
+}
+
+#[test]
+fn test_max_mint_per_call() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 1_000_000));
+
+        MaxMintPerCall::set(Some(100));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 100));
+        assert_noop!(Multitoken::mint(owner.clone(), 2, 0, 101), Error::<Test>::MintAmountTooLarge);
+        assert_ok!(Multitoken::mint_batch(owner.clone(), 2, vec![0, 0], vec![100, 100]));
+        assert_noop!(
+            Multitoken::mint_batch(owner.clone(), 2, vec![0, 0], vec![100, 101]),
+            Error::<Test>::MintAmountTooLarge
+        );
+        let signature = TestSignature(1, Multitoken::claim_payload(&0, &2, 101, 0));
+        assert_noop!(
+            Multitoken::claim_mint(RuntimeOrigin::signed(2), 0, 101, signature),
+            Error::<Test>::MintAmountTooLarge
+        );
+        assert_noop!(
+            Multitoken::bridge_mint(RuntimeOrigin::root(), 2, 0, 101, sp_core::H256::zero()),
+            Error::<Test>::MintAmountTooLarge
+        );
+
+        MaxMintPerCall::set(None);
+        assert_ok!(Multitoken::mint(owner, 2, 0, 1_000_000));
+        assert_eq!(Multitoken::balance_of(&2, &0), 2_000_300);
+    });
+}
+
+#[test]
+fn test_apply_event_to_balances_matches_storage() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 100));
+        assert_ok!(Multitoken::mint_batch(owner.clone(), 3, vec![0, 1], vec![50, 70]));
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(2), 2, 4, 0, 30));
+        assert_ok!(Multitoken::safe_batch_transfer_from(
+            RuntimeOrigin::signed(3),
+            3,
+            4,
+            vec![0, 1],
+            vec![50, 20]
+        ));
+        assert_ok!(Multitoken::burn(RuntimeOrigin::signed(4), 0, 80));
+        assert_ok!(Multitoken::mint(owner.clone(), 5, 2, 10));
+        assert_ok!(Multitoken::merge_collections(owner, 1, 2, 10));
+
+        assert_events_match_balances();
+        let mut state = BTreeMap::new();
+        for record in System::events() {
+            if let RuntimeEvent::Multitoken(event) = record.event {
+                Multitoken::apply_event_to_balances(&mut state, &event);
+            }
+        }
+        assert_eq!(state.get(&(0, 2)), Some(&70));
+        assert_eq!(state.get(&(0, 4)), None);
+        assert_eq!(state.get(&(2, 3)), Some(&50));
+        assert_eq!(state.get(&(2, 4)), Some(&20));
+    });
+}
+
+#[test]
+fn test_remaining_collection_ids() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_eq!(Multitoken::remaining_collection_ids(), Some(u64::MAX as u128));
+
+        NextCollectionId::<Test>::put(u64::MAX - 2);
+        assert_eq!(Multitoken::remaining_collection_ids(), Some(2));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_eq!(Multitoken::remaining_collection_ids(), Some(1));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_eq!(Multitoken::remaining_collection_ids(), Some(0));
+        assert_eq!(Multitoken::next_collection_id(), u64::MAX);
+
+        assert_noop!(Multitoken::create(owner, true), Error::<Test>::CollectionIdsExhausted);
+        assert_eq!(Multitoken::collections(u64::MAX), None);
+    });
+}
+
+#[test]
+fn test_transfer_moves_signer_tokens() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner, 2, 0, 100));
+
+        assert!(!Multitoken::is_approved_for_all(&2, &2));
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 40));
+        System::assert_last_event(
+            Event::TransferSingle {
+                operator: 2,
+                from: Some(2),
+                to: Some(3),
+                id: 0,
+                value: 40,
+                fungible: true,
+            }
+            .into(),
+        );
+        assert_eq!(Multitoken::balance_of(&2, &0), 60);
+        assert_eq!(Multitoken::balance_of(&3, &0), 40);
+
+        assert_noop!(
+            Multitoken::transfer(RuntimeOrigin::signed(3), 2, 0, 41),
+            Error::<Test>::InsufficientBalance
+        );
+        assert_events_match_balances();
+    });
+}
+
+#[test]
+fn test_collection_details_migration() {
+    new_test_ext().execute_with(|| {
+        v3::Collections::<Test>::insert(0, 1);
+        v3::Collections::<Test>::insert(1, 2);
+        v3::TotalSupply::<Test>::insert(0, 10);
+        v3::TotalSupply::<Test>::insert(1, 1);
+        v3::TotalSupply::<Test>::insert(7, 5);
+        v3::Fungible::<Test>::insert(0, true);
+        v3::Fungible::<Test>::insert(1, false);
+        Balances::<Test>::insert(0, 3, 10);
+        Balances::<Test>::insert(1, 3, 1);
+        StorageVersion::new(2).put::<Multitoken>();
+
+        Multitoken::on_runtime_upgrade();
+
+        assert_eq!(Multitoken::on_chain_storage_version(), 4);
+        assert_eq!(
+            Multitoken::collection_details(0),
+            Some(CollectionDetails {
+                owner: 1,
+                total_supply: 10,
+                max_supply: None,
+                frozen: false,
+                fungible: true,
+            })
+        );
+        assert_eq!(
+            Multitoken::collection_details(1),
+            Some(CollectionDetails {
+                owner: 2,
+                total_supply: 1,
+                max_supply: None,
+                frozen: false,
+                fungible: false,
+            })
+        );
+        assert_eq!(CollectionsV2::<Test>::count(), 2);
+        assert_eq!(Multitoken::owner_collection_count(1), 1);
+        assert_eq!(Multitoken::owner_collection_count(2), 1);
+        assert_eq!(v3::Collections::<Test>::iter().count(), 0);
+        assert_eq!(v3::TotalSupply::<Test>::iter().count(), 0);
+        assert_eq!(v3::Fungible::<Test>::iter().count(), 0);
+        assert!(ZeroBalanceSweepCursor::<Test>::get().is_none());
+
+        // The migrated details back the regular code paths.
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(1), 3, 0, 5));
+        assert_eq!(Multitoken::total_supply(0), 15);
+        assert_noop!(
+            Multitoken::mint(RuntimeOrigin::signed(2), 3, 1, 1),
+            Error::<Test>::NotFungible
+        );
+    });
+}
+
+#[test]
+fn test_collection_details_migration_keeps_baseline_collections_fungible() {
+    new_test_ext().execute_with(|| {
+        // A collection of the first version: no `Fungible` entry and several holders.
+        v3::Collections::<Test>::insert(0, 1);
+        v3::TotalSupply::<Test>::insert(0, 30);
+        Balances::<Test>::insert(0, 2, 10);
+        Balances::<Test>::insert(0, 3, 20);
+        StorageVersion::new(2).put::<Multitoken>();
+
+        Multitoken::on_runtime_upgrade();
+
+        assert!(Multitoken::collection_details(0).is_some_and(|details| details.fungible));
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(1), 4, 0, 5));
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(3), 2, 0, 5));
+        assert_eq!(Multitoken::balance_of(&2, &0), 15);
+        assert_eq!(Multitoken::total_supply(0), 35);
+    });
+}
+
+#[test]
+fn test_collection_details_migration_rebuilds_missing_supplies() {
+    new_test_ext().execute_with(|| {
+        // Collection 0 predates `TotalSupply`, collection 1 does not.
+        v3::Collections::<Test>::insert(0, 1);
+        v3::Collections::<Test>::insert(1, 1);
+        v3::TotalSupply::<Test>::insert(1, 4);
+        Balances::<Test>::insert(0, 2, 10);
+        Balances::<Test>::insert(0, 3, 20);
+        Balances::<Test>::insert(1, 2, 4);
+        StorageVersion::new(2).put::<Multitoken>();
+
+        Multitoken::on_runtime_upgrade();
+
+        assert_eq!(Multitoken::total_supply(0), 30);
+        assert_eq!(Multitoken::total_supply(1), 4);
+        assert_ok!(Multitoken::burn(RuntimeOrigin::signed(3), 0, 20));
+        assert_eq!(Multitoken::total_supply(0), 10);
+    });
+}
+
+#[test]
+fn test_collection_details_migration_resumes_zero_balance_sweep() {
+    new_test_ext().execute_with(|| {
+        v3::Collections::<Test>::insert(0, 1);
+        v3::Fungible::<Test>::insert(0, true);
+        Balances::<Test>::insert(0, 2, 10);
+        Balances::<Test>::insert(0, 3, 0);
+        StorageVersion::new(1).put::<Multitoken>();
+
+        Multitoken::on_runtime_upgrade();
+
+        assert_eq!(Multitoken::on_chain_storage_version(), 4);
+        assert!(ZeroBalanceSweepCursor::<Test>::get().is_some());
+
+        Multitoken::on_idle(1, Weight::MAX);
+        assert!(ZeroBalanceSweepCursor::<Test>::get().is_none());
+        assert_eq!(Multitoken::on_chain_storage_version(), 4);
+        assert_eq!(Balances::<Test>::iter().collect::<Vec<_>>(), vec![(0, 2, 10)]);
+    });
+}
+
+#[test]
+fn test_holder_exited_event() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner, 2, 0, 100));
+        EmitHolderExitEvents::set(true);
+
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 40));
+        System::assert_last_event(
+            Event::TransferSingle {
+                operator: 2,
+                from: Some(2),
+                to: Some(3),
+                id: 0,
+                value: 40,
+                fungible: true,
+            }
+            .into(),
+        );
+
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 60));
+        System::assert_last_event(Event::HolderExited { id: 0, account: 2 }.into());
+
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(3), 3, 0, 100));
+        System::assert_last_event(
+            Event::TransferSingle {
+                operator: 3,
+                from: Some(3),
+                to: Some(3),
+                id: 0,
+                value: 100,
+                fungible: true,
+            }
+            .into(),
+        );
+
+        EmitHolderExitEvents::set(false);
+        assert_ok!(Multitoken::burn(RuntimeOrigin::signed(3), 0, 100));
+        System::assert_last_event(
+            Event::TransferSingle {
+                operator: 3,
+                from: Some(3),
+                to: None,
+                id: 0,
+                value: 100,
+                fungible: true,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_collection_hooks() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint_batch(owner.clone(), 2, vec![0, 1], vec![10, 10]));
+
+        assert_noop!(
+            Multitoken::set_collection_hook(RuntimeOrigin::signed(2), 0, Some(0)),
+            Error::<Test>::InvalidOwner
+        );
+        assert_ok!(Multitoken::set_collection_hook(owner.clone(), 0, Some(0)));
+        System::assert_last_event(
+            Event::CollectionHookSet {
+                id: 0,
+                hook: Some(0),
+            }
+            .into(),
+        );
+        assert_ok!(Multitoken::set_collection_hook(owner.clone(), 1, Some(1)));
+        assert_eq!(Multitoken::collection_hook(0), Some(0));
+
+        let rejected = DispatchError::Other("transfer rejected by collection hook");
+        assert_noop!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 5), rejected);
+        assert_noop!(
+            Multitoken::safe_batch_transfer_from(
+                RuntimeOrigin::signed(2),
+                2,
+                3,
+                vec![1, 0],
+                vec![5, 5]
+            ),
+            rejected
+        );
+        assert_noop!(Multitoken::mint(owner.clone(), 2, 0, 1), rejected);
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 1, 5));
+        assert_eq!(Multitoken::balance_of(&3, &1), 5);
+
+        assert_ok!(Multitoken::set_collection_hook(owner, 0, None));
+        assert_eq!(Multitoken::collection_hook(0), None);
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 5));
+        assert_eq!(Multitoken::balance_of(&3, &0), 5);
+    });
+}
+
+#[test]
+fn test_burn_batch_from() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint_batch(owner, 2, vec![0, 1], vec![10, 20]));
+
+        assert_noop!(
+            Multitoken::burn_batch_from(RuntimeOrigin::signed(3), 2, vec![0, 1], vec![5, 5]),
+            Error::<Test>::InsufficientApprovalForAll
+        );
+
+        assert_ok!(Multitoken::set_approval_for_all(RuntimeOrigin::signed(2), 3, true, None));
+        assert_noop!(
+            Multitoken::burn_batch_from(RuntimeOrigin::signed(3), 2, vec![0, 1], vec![5, 21]),
+            Error::<Test>::InsufficientBalanceInBatch { index: 1 }
+        );
+
+        assert_ok!(Multitoken::burn_batch_from(
+            RuntimeOrigin::signed(3),
+            2,
+            vec![0, 1],
+            vec![4, 20]
+        ));
+        System::assert_last_event(
+            Event::TransferBatch {
+                operator: 3,
+                from: Some(2),
+                to: None,
+                ids: BoundedVec::truncate_from(vec![0, 1]),
+                values: BoundedVec::truncate_from(vec![4, 20]),
+                fungible: BoundedVec::truncate_from(vec![true, true]),
+            }
+            .into(),
+        );
+        assert_eq!(Multitoken::balance_of(&2, &0), 6);
+        assert_eq!(Multitoken::balance_of(&2, &1), 0);
+        assert_eq!(Multitoken::total_supply(0), 6);
+        assert_eq!(Multitoken::total_supply(1), 0);
+        assert_events_match_balances();
+    });
+}
+
+#[test]
+fn test_calculate_fee() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Multitoken::calculate_fee(10_000, 250), 250);
+        assert_eq!(Multitoken::calculate_fee(1_000, 500), 50);
+        assert_eq!(Multitoken::calculate_fee(1_000, 0), 0);
+        assert_eq!(Multitoken::calculate_fee(1_000, 10_000), 1_000);
+        assert_eq!(Multitoken::calculate_fee(u64::MAX, 10_000), u64::MAX);
+        assert_eq!(Multitoken::calculate_fee(1_000, u16::MAX), 1_000);
+        // Non-divisible amounts round down.
+        assert_eq!(Multitoken::calculate_fee(999, 250), 24);
+        assert_eq!(Multitoken::calculate_fee(1, 9_999), 0);
+        assert_eq!(Multitoken::calculate_fee(3, 3_333), 0);
+    });
+}
+
+#[test]
+fn test_force_set_balance() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 100));
+        assert_ok!(Multitoken::mint(owner.clone(), 3, 0, 50));
+
+        assert_noop!(Multitoken::force_set_balance(owner, 0, 2, 10), sp_runtime::traits::BadOrigin);
+        assert_noop!(
+            Multitoken::force_set_balance(RuntimeOrigin::root(), 1, 2, 10),
+            Error::<Test>::CollectionDoesNotExist
+        );
+
+        assert_ok!(Multitoken::force_set_balance(RuntimeOrigin::root(), 0, 2, 130));
+        System::assert_last_event(
+            Event::BalanceForceSet {
+                id: 0,
+                account: 2,
+                old: 100,
+                new: 130,
+            }
+            .into(),
+        );
+        assert_eq!(Multitoken::balance_of(&2, &0), 130);
+        assert_eq!(Multitoken::total_supply(0), 180);
+
+        assert_ok!(Multitoken::force_set_balance(RuntimeOrigin::root(), 0, 2, 30));
+        assert_eq!(Multitoken::balance_of(&2, &0), 30);
+        assert_eq!(Multitoken::total_supply(0), 80);
+
+        assert_ok!(Multitoken::force_set_balance(RuntimeOrigin::root(), 0, 3, 0));
+        System::assert_last_event(
+            Event::BalanceForceSet {
+                id: 0,
+                account: 3,
+                old: 50,
+                new: 0,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::<Test>::get(0, 3), None);
+        assert_eq!(Multitoken::total_supply(0), 30);
+        assert_events_match_balances();
+    });
+}
+
+#[test]
+fn test_destroy_collection_removes_every_entry() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        for id in 0..2 {
+            assert_ok!(Multitoken::mint(owner.clone(), 2, id, 10));
+            assert_ok!(Multitoken::allow_claim(owner.clone(), id, 3, 5));
+            assert_ok!(Multitoken::allow_recipient(owner.clone(), id, 2, true));
+            assert_ok!(Multitoken::set_recipient_allowlist_enforced(owner.clone(), id, true));
+            assert_ok!(Multitoken::set_collection_hook(owner.clone(), id, Some(1)));
+            assert_ok!(Multitoken::freeze_account(owner.clone(), id, 4, true));
+            assert_ok!(Multitoken::approve(RuntimeOrigin::signed(2), 5, id, 5, None));
+            assert_ok!(Multitoken::set_approval_for_collection(
+                RuntimeOrigin::signed(2),
+                5,
+                id,
+                true,
+                None
+            ));
+            assert_ok!(Multitoken::approve_transfer_once(RuntimeOrigin::signed(2), 5, id, 5));
+        }
+
+        assert_noop!(
+            Multitoken::destroy_collection(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::InvalidOwner
+        );
+        assert_noop!(
+            Multitoken::destroy_collection(owner.clone(), 0),
+            Error::<Test>::CollectionNotEmpty
+        );
+
+        assert_ok!(Multitoken::burn(RuntimeOrigin::signed(2), 0, 10));
+        assert_ok!(Multitoken::destroy_collection(owner.clone(), 0));
+        System::assert_last_event(Event::DestructionStarted { id: 0 }.into());
+        assert_noop!(Multitoken::destroy_collection(owner.clone(), 0), Error::<Test>::Destroying);
+        complete_destroy(0);
+        System::assert_last_event(Event::CollectionDestroyed { id: 0 }.into());
+        // Merging removes the source collection in the same way.
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::merge_collections(owner, 1, 2, 10));
+        complete_destroy(1);
+
+        for id in 0..2 {
+            assert_eq!(Multitoken::collection_details(id), None);
+            assert_eq!(Multitoken::collection_hook(id), None);
+            assert!(!Multitoken::recipient_allowlist_enforced(id));
+            assert_eq!(RecipientAllowlist::<Test>::iter_prefix(id).count(), 0);
+            assert_eq!(Claimable::<Test>::iter_prefix(id).count(), 0);
+            assert_eq!(FrozenAccounts::<Test>::iter_prefix(id).count(), 0);
+            assert_eq!(Balances::<Test>::iter_prefix(id).count(), 0);
+            // Approvals are removed too, so that a recreated collection doesn't inherit them.
+            assert_eq!(Allowances::<Test>::get(2, (id, 5)), None);
+            assert_eq!(CollectionApprovals::<Test>::get(2, (id, 5)), None);
+            assert_eq!(OneShotApprovals::<Test>::get(2, (id, 5)), None);
+        }
+        assert_eq!(CollectionsV2::<Test>::count(), 1);
+    });
+}
+
+#[test]
+fn test_is_frozen() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint_batch(owner.clone(), 2, vec![0, 1], vec![10, 10]));
+        assert!(!Multitoken::is_frozen(&0, &2));
+
+        // Global pause.
+        assert_noop!(Multitoken::set_paused(owner.clone(), true), sp_runtime::traits::BadOrigin);
+        assert_ok!(Multitoken::set_paused(RuntimeOrigin::root(), true));
+        System::assert_last_event(Event::PauseSet { paused: true }.into());
+        assert!(Multitoken::is_frozen(&0, &2));
+        assert!(Multitoken::is_frozen(&1, &3));
+        assert_noop!(
+            Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 1),
+            Error::<Test>::Frozen
+        );
+        assert_ok!(Multitoken::set_paused(RuntimeOrigin::root(), false));
+
+        // Collection freeze.
+        assert_noop!(
+            Multitoken::freeze_collection(RuntimeOrigin::signed(2), 0, true),
+            Error::<Test>::InvalidOwner
+        );
+        assert_ok!(Multitoken::freeze_collection(owner.clone(), 0, true));
+        System::assert_last_event(
+            Event::CollectionFrozen {
+                id: 0,
+                frozen: true,
+            }
+            .into(),
+        );
+        assert!(Multitoken::is_frozen(&0, &2));
+        assert!(!Multitoken::is_frozen(&1, &2));
+        assert_noop!(Multitoken::burn(RuntimeOrigin::signed(2), 0, 1), Error::<Test>::Frozen);
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 1, 1));
+
+        // Account freeze.
+        assert_ok!(Multitoken::freeze_account(owner.clone(), 1, 2, true));
+        System::assert_last_event(
+            Event::AccountFrozen {
+                id: 1,
+                account: 2,
+                frozen: true,
+            }
+            .into(),
+        );
+        assert!(Multitoken::is_frozen(&1, &2));
+        assert!(!Multitoken::is_frozen(&1, &3));
+        assert_noop!(
+            Multitoken::transfer(RuntimeOrigin::signed(2), 3, 1, 1),
+            Error::<Test>::Frozen
+        );
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(3), 2, 1, 1));
+
+        // Every source has to be lifted before the tokens can move again.
+        assert_ok!(Multitoken::set_paused(RuntimeOrigin::root(), true));
+        assert_ok!(Multitoken::freeze_account(owner.clone(), 0, 2, true));
+        assert_ok!(Multitoken::set_paused(RuntimeOrigin::root(), false));
+        assert_ok!(Multitoken::freeze_collection(owner.clone(), 0, false));
+        assert!(Multitoken::is_frozen(&0, &2));
+        assert_ok!(Multitoken::freeze_account(owner, 0, 2, false));
+        assert!(!Multitoken::is_frozen(&0, &2));
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 1));
+    });
+}
+
+#[test]
+fn test_mint_batch_with_uris() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+
+        assert_noop!(
+            Multitoken::mint_batch_with_uris(
+                owner.clone(),
+                2,
+                vec![0, 1],
+                vec![10, 20],
+                vec![b"ipfs://a".to_vec()]
+            ),
+            Error::<Test>::MismatchedArrayLength
+        );
+        assert_noop!(
+            Multitoken::mint_batch_with_uris(
+                owner.clone(),
+                2,
+                vec![0, 1],
+                vec![10, 20],
+                vec![b"ipfs://a".to_vec(), vec![0; 17]]
+            ),
+            Error::<Test>::UriTooLong
+        );
+        assert_ok!(Multitoken::freeze_collection(owner.clone(), 1, true));
+        assert_noop!(
+            Multitoken::mint_batch_with_uris(
+                owner.clone(),
+                2,
+                vec![0, 1],
+                vec![10, 20],
+                vec![b"ipfs://a".to_vec(), b"ipfs://b".to_vec()]
+            ),
+            Error::<Test>::Frozen
+        );
+        assert_ok!(Multitoken::freeze_collection(owner.clone(), 1, false));
+
+        assert_ok!(Multitoken::mint_batch_with_uris(
+            owner,
+            2,
+            vec![0, 1],
+            vec![10, 20],
+            vec![b"ipfs://a".to_vec(), b"ipfs://b".to_vec()]
+        ));
+        assert_eq!(Multitoken::balance_of(&2, &0), 10);
+        assert_eq!(Multitoken::balance_of(&2, &1), 20);
+        assert_eq!(Multitoken::collection_uri(0).unwrap().into_inner(), b"ipfs://a".to_vec());
+        assert_eq!(Multitoken::collection_uri(1).unwrap().into_inner(), b"ipfs://b".to_vec());
+    });
+}
+
+#[test]
+fn test_transfer_internal() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner, 2, 0, 100));
+        assert!(!Multitoken::is_approved_for_all(&2, &3));
+
+        assert_ok!(Multitoken::transfer_internal(&2, &3, &0, 60));
+        System::assert_last_event(
+            Event::TransferSingle {
+                operator: 2,
+                from: Some(2),
+                to: Some(3),
+                id: 0,
+                value: 60,
+                fungible: true,
+            }
+            .into(),
+        );
+        assert_eq!(Multitoken::balance_of(&2, &0), 40);
+        assert_eq!(Multitoken::balance_of(&3, &0), 60);
+
+        assert_ok!(Multitoken::transfer_internal(&2, &3, &0, 40));
+        assert_eq!(Balances::<Test>::get(0, 2), None);
+        assert_eq!(Multitoken::balance_of(&3, &0), 100);
+        assert_eq!(Multitoken::total_supply(0), 100);
+
+        assert_noop!(
+            Multitoken::transfer_internal(&2, &3, &0, 1),
+            Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn test_transfer_events_carry_fungibility() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), false));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 1));
+        System::assert_last_event(
+            Event::TransferSingle {
+                operator: 1,
+                from: None,
+                to: Some(2),
+                id: 0,
+                value: 1,
+                fungible: false,
+            }
+            .into(),
+        );
+        assert_ok!(Multitoken::mint(owner, 2, 1, 10));
+        System::assert_last_event(
+            Event::TransferSingle {
+                operator: 1,
+                from: None,
+                to: Some(2),
+                id: 1,
+                value: 10,
+                fungible: true,
+            }
+            .into(),
+        );
+
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 1));
+        System::assert_last_event(
+            Event::TransferSingle {
+                operator: 2,
+                from: Some(2),
+                to: Some(3),
+                id: 0,
+                value: 1,
+                fungible: false,
+            }
+            .into(),
+        );
+
+        assert_ok!(Multitoken::safe_batch_transfer_from(
+            RuntimeOrigin::signed(3),
+            3,
+            2,
+            vec![0],
+            vec![1]
+        ));
+        assert_ok!(Multitoken::burn_batch(RuntimeOrigin::signed(2), vec![1, 0], vec![4, 1]));
+        System::assert_last_event(
+            Event::TransferBatch {
+                operator: 2,
+                from: Some(2),
+                to: None,
+                ids: BoundedVec::truncate_from(vec![1, 0]),
+                values: BoundedVec::truncate_from(vec![4, 1]),
+                fungible: BoundedVec::truncate_from(vec![true, false]),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_set_metadata_validates_strings() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+
+        assert_ok!(Multitoken::set_metadata(
+            owner.clone(),
+            0,
+            "Café ☕".as_bytes().to_vec(),
+            b"CAFE".to_vec()
+        ));
+        let metadata = Multitoken::metadata(0).unwrap();
+        assert_eq!(metadata.name.into_inner(), "Café ☕".as_bytes().to_vec());
+        assert_eq!(metadata.symbol.into_inner(), b"CAFE".to_vec());
+
+        assert_noop!(
+            Multitoken::set_metadata(owner.clone(), 0, vec![0xff, 0xfe], b"CAFE".to_vec()),
+            Error::<Test>::InvalidMetadata
+        );
+        assert_noop!(
+            Multitoken::set_metadata(owner.clone(), 0, b"Cafe".to_vec(), vec![0xc3]),
+            Error::<Test>::InvalidMetadata
+        );
+        assert_noop!(
+            Multitoken::set_metadata(owner.clone(), 0, b"Ca\nfe".to_vec(), b"CAFE".to_vec()),
+            Error::<Test>::InvalidMetadata
+        );
+        assert_noop!(
+            Multitoken::set_metadata(owner.clone(), 0, b"Cafe".to_vec(), b"CA\x07FE".to_vec()),
+            Error::<Test>::InvalidMetadata
+        );
+        assert_noop!(
+            Multitoken::set_metadata(owner.clone(), 0, vec![b'a'; 17], b"CAFE".to_vec()),
+            Error::<Test>::InvalidMetadata
+        );
+        assert_noop!(
+            Multitoken::set_metadata(
+                RuntimeOrigin::signed(2),
+                0,
+                b"Cafe".to_vec(),
+                b"CAFE".to_vec()
+            ),
+            Error::<Test>::InvalidOwner
+        );
+    });
+}
+
+#[test]
+fn test_owner_collection_count() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_eq!(Multitoken::owner_collection_count(1), 0);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(2), true));
+        assert_eq!(Multitoken::owner_collection_count(1), 3);
+        assert_eq!(Multitoken::owner_collection_count(2), 1);
+
+        assert_ok!(Multitoken::destroy_collection(owner.clone(), 0));
+        complete_destroy(0);
+        assert_eq!(Multitoken::owner_collection_count(1), 2);
+
+        assert_ok!(Multitoken::mint(owner.clone(), 3, 1, 10));
+        assert_ok!(Multitoken::merge_collections(owner, 1, 2, 10));
+        complete_destroy(1);
+        assert_eq!(Multitoken::owner_collection_count(1), 1);
+        assert_eq!(Multitoken::owner_collection_count(2), 1);
+    });
+}
+
+#[test]
+fn test_reaping_an_account_revokes_its_approvals() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Multitoken::set_approval_for_all(RuntimeOrigin::signed(2), 3, true, None));
+        assert_ok!(Multitoken::set_approval_for_all(RuntimeOrigin::signed(2), 4, true, None));
+        assert_ok!(Multitoken::set_approval_for_all(RuntimeOrigin::signed(5), 2, true, None));
+        assert_ok!(Multitoken::set_approval_for_all(RuntimeOrigin::signed(5), 3, true, None));
+
+        System::inc_providers(&2);
+        assert_ok!(System::dec_providers(&2));
+        assert!(ReapedAccounts::<Test>::contains_key(2));
+        // Nothing is removed until `on_idle` runs.
+        assert!(Multitoken::is_approved_for_all(&2, &3));
+
+        Multitoken::on_idle(1, Weight::MAX);
+        assert!(!ReapedAccounts::<Test>::contains_key(2));
+        assert!(!Multitoken::is_approved_for_all(&2, &3));
+        assert!(!Multitoken::is_approved_for_all(&2, &4));
+        assert!(!Multitoken::is_approved_for_all(&5, &2));
+        assert!(Multitoken::is_approved_for_all(&5, &3));
+        assert_eq!(ApprovalsByOperator::<Test>::iter().collect::<Vec<_>>(), vec![(3, 5, ())]);
+        for (account, operator) in [(2, 3), (2, 4), (5, 2)] {
+            System::assert_has_event(
+                Event::ApprovalForAll {
+                    account,
+                    operator,
+                    approved: false,
+                }
+                .into(),
+            );
+        }
+
+        // Accounts without approvals are not queued.
+        System::inc_providers(&6);
+        assert_ok!(System::dec_providers(&6));
+        assert!(!ReapedAccounts::<Test>::contains_key(6));
+    });
+}
+
+#[test]
+fn test_reaped_approvals_cleanup_respects_weight() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for operator in 3..6 {
+            assert_ok!(Multitoken::set_approval_for_all(
+                RuntimeOrigin::signed(2),
+                operator,
+                true,
+                None
+            ));
+        }
+        System::inc_providers(&2);
+        assert_ok!(System::dec_providers(&2));
+
+        let db_weight = <Test as frame_system::Config>::DbWeight::get();
+        // The zero balance sweep reads two items before the cleanup runs.
+        let budget = db_weight
+            .reads(3)
+            .saturating_add(db_weight.reads_writes(1, 2));
+        Multitoken::on_idle(1, budget);
+        assert_eq!(OperatorApprovals::<Test>::iter_prefix(2).count(), 2);
+        assert!(ReapedAccounts::<Test>::contains_key(2));
+
+        while ReapedAccounts::<Test>::contains_key(2) {
+            Multitoken::on_idle(1, budget);
+        }
+        assert_eq!(OperatorApprovals::<Test>::iter_prefix(2).count(), 0);
+    });
+}
+
+#[test]
+fn test_display_balance_uses_collection_scale() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 1_500));
+        assert_eq!(Multitoken::display_balance(&0, &2), (1_500, 0));
+
+        assert_noop!(
+            Multitoken::set_scale(RuntimeOrigin::signed(2), 0, 3),
+            Error::<Test>::InvalidOwner
+        );
+        assert_ok!(Multitoken::set_scale(owner, 0, 3));
+        System::assert_last_event(Event::ScaleSet { id: 0, scale: 3 }.into());
+        assert_eq!(Multitoken::display_balance(&0, &2), (1_500, 3));
+        assert_eq!(Multitoken::balance_of(&2, &0), 1_500);
+        assert_eq!(Multitoken::total_supply(0), 1_500);
+
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 500));
+        assert_eq!(Multitoken::display_balance(&0, &3), (500, 3));
+    });
+}
+
+#[test]
+fn test_bridge_mint_rejects_replays() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        let message_hash = sp_core::H256::repeat_byte(1);
+
+        assert_noop!(
+            Multitoken::bridge_mint(RuntimeOrigin::signed(1), 2, 0, 100, message_hash),
+            sp_runtime::traits::BadOrigin
+        );
+        assert_ok!(Multitoken::bridge_mint(RuntimeOrigin::root(), 2, 0, 100, message_hash));
+        System::assert_last_event(Event::BridgeMessageProcessed { message_hash }.into());
+        assert_eq!(Multitoken::balance_of(&2, &0), 100);
+
+        assert_noop!(
+            Multitoken::bridge_mint(RuntimeOrigin::root(), 2, 0, 100, message_hash),
+            Error::<Test>::DuplicateBridgeMessage
+        );
+        assert_ok!(Multitoken::bridge_mint(
+            RuntimeOrigin::root(),
+            2,
+            0,
+            50,
+            sp_core::H256::repeat_byte(2)
+        ));
+        assert_eq!(Multitoken::balance_of(&2, &0), 150);
+        assert_eq!(Multitoken::total_supply(0), 150);
+    });
+}
+
+#[test]
+fn test_capped_approval() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint_batch(owner, 2, vec![0, 1], vec![100, 100]));
+
+        assert_ok!(Multitoken::set_capped_approval(RuntimeOrigin::signed(2), 3, 50));
+        System::assert_last_event(
+            Event::CappedApprovalSet {
+                account: 2,
+                operator: 3,
+                cap: 50,
+            }
+            .into(),
+        );
+        assert!(Multitoken::is_approved_for_all(&2, &3));
+
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(3), 2, 4, 0, 20));
+        assert_ok!(Multitoken::safe_batch_transfer_from(
+            RuntimeOrigin::signed(3),
+            2,
+            4,
+            vec![0, 1],
+            vec![10, 15]
+        ));
+        assert_eq!(Multitoken::approval_cap(2, 3), Some(5));
+
+        // Owner transfers do not consume the cap.
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 4, 1, 30));
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(2), 2, 4, 1, 30));
+        assert_eq!(Multitoken::approval_cap(2, 3), Some(5));
+
+        assert_noop!(
+            Multitoken::safe_transfer_from(RuntimeOrigin::signed(3), 2, 4, 1, 6),
+            Error::<Test>::ApprovalCapExceeded
+        );
+        assert_noop!(
+            Multitoken::burn_batch_from(RuntimeOrigin::signed(3), 2, vec![0, 1], vec![3, 3]),
+            Error::<Test>::ApprovalCapExceeded
+        );
+        assert_ok!(Multitoken::burn_batch_from(RuntimeOrigin::signed(3), 2, vec![1], vec![5]));
+        assert_eq!(Multitoken::approval_cap(2, 3), Some(0));
+        assert_noop!(
+            Multitoken::safe_transfer_from(RuntimeOrigin::signed(3), 2, 4, 0, 1),
+            Error::<Test>::ApprovalCapExceeded
+        );
+
+        // A plain approval lifts the cap, revoking removes it.
+        assert_ok!(Multitoken::set_approval_for_all(RuntimeOrigin::signed(2), 3, true, None));
+        assert_eq!(Multitoken::approval_cap(2, 3), None);
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(3), 2, 4, 0, 70));
+        assert_ok!(Multitoken::set_capped_approval(RuntimeOrigin::signed(2), 3, 10));
+        assert_ok!(Multitoken::set_approval_for_all(RuntimeOrigin::signed(2), 3, false, None));
+        assert_eq!(Multitoken::approval_cap(2, 3), None);
+    });
+}
+
+#[test]
+fn test_mint_idempotent_rejects_reused_nonces() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(2), true));
+
+        assert_ok!(Multitoken::mint_idempotent(owner.clone(), 3, 0, 100, 7));
+        assert_noop!(
+            Multitoken::mint_idempotent(owner.clone(), 3, 0, 100, 7),
+            Error::<Test>::DuplicateClientNonce
+        );
+        assert_eq!(Multitoken::balance_of(&3, &0), 100);
+
+        assert_ok!(Multitoken::mint_idempotent(owner, 3, 0, 100, 8));
+        // Nonces are tracked per caller.
+        assert_ok!(Multitoken::mint_idempotent(RuntimeOrigin::signed(2), 3, 1, 100, 7));
+        assert_eq!(Multitoken::balance_of(&3, &0), 200);
+        assert_eq!(Multitoken::balance_of(&3, &1), 100);
+    });
+}
+
+#[test]
+fn test_top_holders() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        for (account, amount) in [(2, 50), (3, 10), (4, 70), (5, 30), (6, 50)] {
+            assert_ok!(Multitoken::mint(owner.clone(), account, 0, amount));
+        }
+        assert_ok!(Multitoken::mint(owner, 7, 1, 1_000));
+
+        assert_eq!(Multitoken::top_holders(&0, 3), vec![(4, 70), (6, 50), (2, 50)]);
+        assert_eq!(
+            Multitoken::top_holders(&0, 10),
+            vec![(4, 70), (6, 50), (2, 50), (5, 30), (3, 10)]
+        );
+        assert_eq!(Multitoken::top_holders(&0, 0), vec![]);
+        assert_eq!(Multitoken::top_holders(&1, 3), vec![(7, 1_000)]);
+        assert_eq!(Multitoken::top_holders(&2, 3), vec![]);
+    });
+}
+
+#[test]
+fn test_ensure_collection_and_mint() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_noop!(
+            Multitoken::ensure_collection_and_mint(owner.clone(), 5, 2, 10),
+            Error::<Test>::CustomIdsDisabled
+        );
+
+        CustomCollectionIds::set(true);
+        assert_ok!(Multitoken::ensure_collection_and_mint(owner.clone(), 5, 2, 10));
+        System::assert_has_event(Event::CollectionCreated { id: 5, owner: 1 }.into());
+        assert_eq!(Multitoken::collection_owner(&5), Some(1));
+        assert_eq!(Multitoken::balance_of(&2, &5), 10);
+
+        assert_ok!(Multitoken::ensure_collection_and_mint(owner.clone(), 5, 2, 15));
+        assert_eq!(Multitoken::balance_of(&2, &5), 25);
+        assert_eq!(Multitoken::total_supply(5), 25);
+        assert_eq!(Multitoken::owner_collection_count(1), 1);
+
+        assert_noop!(
+            Multitoken::ensure_collection_and_mint(RuntimeOrigin::signed(2), 5, 2, 10),
+            Error::<Test>::InvalidOwner
+        );
+
+        // Sequential ids skip the ones taken by custom ids.
+        assert_ok!(Multitoken::ensure_collection_and_mint(owner.clone(), 1, 2, 10));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner, true));
+        assert_eq!(Multitoken::collection_owner(&0), Some(1));
+        assert_eq!(Multitoken::collection_owner(&2), Some(1));
+        assert_eq!(Multitoken::next_collection_id(), 3);
+    });
+}
+
+#[test]
+fn test_transfer_to_null_account_burns() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        NullAccount::set(Some(0));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(1), 2, 0, 100));
+
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 0, 0, 40));
+        System::assert_last_event(
+            Event::TransferSingle {
+                operator: 2,
+                from: Some(2),
+                to: None,
+                id: 0,
+                value: 40,
+                fungible: true,
+            }
+            .into(),
+        );
+        assert_eq!(Multitoken::balance_of(&2, &0), 60);
+        assert_eq!(Multitoken::balance_of(&0, &0), 0);
+        assert_eq!(Multitoken::total_supply(0), 60);
+
+        assert_ok!(Multitoken::safe_batch_transfer_from(
+            RuntimeOrigin::signed(2),
+            2,
+            0,
+            vec![0, 0],
+            vec![10, 20]
+        ));
+        assert_eq!(Multitoken::balance_of(&0, &0), 0);
+        assert_eq!(Multitoken::total_supply(0), 30);
+        assert_events_match_balances();
+    });
+}
+
+#[test]
+fn test_try_transfer() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(1), 2, 0, 100));
+
+        assert!(Multitoken::try_transfer(&2, &3, &0, 40));
+        assert_eq!(Multitoken::balance_of(&2, &0), 60);
+        assert_eq!(Multitoken::balance_of(&3, &0), 40);
+
+        assert_storage_noop!(assert!(!Multitoken::try_transfer(&2, &3, &0, 61)));
+        assert!(!Multitoken::try_transfer(&2, &3, &1, 1));
+        assert_eq!(Multitoken::balance_of(&2, &0), 60);
+        assert_eq!(Multitoken::balance_of(&3, &0), 40);
+    });
+}
+
+#[test]
+fn test_created_at() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert_eq!(Multitoken::created_at(0), Some(System::block_number()));
+        assert_eq!(Multitoken::created_at(1), None);
+
+        System::set_block_number(7);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), false));
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(1), 2, 0, 100));
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 50));
+        assert_eq!(Multitoken::created_at(0), Some(3));
+        assert_eq!(Multitoken::created_at(1), Some(7));
+    });
+}
+
+#[test]
+fn test_distribute() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(1), 1, 0, 100));
+
+        assert_ok!(Multitoken::distribute(RuntimeOrigin::signed(1), 0, vec![(2, 10), (3, 30)]));
+        assert_eq!(Multitoken::balance_of(&1, &0), 60);
+        assert_eq!(Multitoken::balance_of(&2, &0), 10);
+        assert_eq!(Multitoken::balance_of(&3, &0), 30);
+        System::assert_last_event(
+            Event::TransferSingle {
+                operator: 1,
+                from: Some(1),
+                to: Some(3),
+                id: 0,
+                value: 30,
+                fungible: true,
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            Multitoken::distribute(RuntimeOrigin::signed(1), 0, vec![(2, 50), (3, 11)]),
+            Error::<Test>::InsufficientBalance
+        );
+        assert_noop!(
+            Multitoken::distribute(RuntimeOrigin::signed(1), 0, vec![]),
+            Error::<Test>::EmptyBatch
+        );
+        let max = <Test as crate::Config>::MaxBatchSize::get() as u64;
+        assert_noop!(
+            Multitoken::distribute(
+                RuntimeOrigin::signed(1),
+                0,
+                (0..=max).map(|i| (10 + i, 0)).collect()
+            ),
+            Error::<Test>::BatchTooLarge
+        );
+        assert_events_match_balances();
+    });
+}
+
+#[test]
+fn test_explain_authorization() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(1), 2, 0, 100));
+
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &2, &0, 10),
+            AuthorizationResult::GrantedAsOwner
+        );
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &3, &0, 10),
+            AuthorizationResult::DeniedNotApproved
+        );
+
+        assert_ok!(Multitoken::set_approval_for_all(RuntimeOrigin::signed(2), 3, true, None));
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &3, &0, 10),
+            AuthorizationResult::GrantedByGlobal
+        );
+
+        assert_ok!(Multitoken::set_capped_approval(RuntimeOrigin::signed(2), 4, 20));
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &4, &0, 20),
+            AuthorizationResult::GrantedByCap { remaining: 20 }
+        );
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &4, &0, 21),
+            AuthorizationResult::DeniedInsufficientAllowance { remaining: 20 }
+        );
+
+        OperatorApprovals::<Test>::insert(
+            2,
+            5,
+            ApprovalRecord {
+                global: true,
+                expires: Some(4),
+            },
+        );
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &5, &0, 10),
+            AuthorizationResult::DeniedExpired
+        );
+
+        assert_ok!(Multitoken::freeze_account(RuntimeOrigin::signed(1), 0, 2, true));
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &2, &0, 10),
+            AuthorizationResult::DeniedFrozen
+        );
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &3, &0, 10),
+            AuthorizationResult::DeniedFrozen
+        );
+    });
+}
+
+#[test]
+fn test_uri_too_long_policy() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        let uri = b"ipfs://0123456789abcdef".to_vec();
+        assert_noop!(
+            Multitoken::set_uri(RuntimeOrigin::signed(1), 0, uri.clone()),
+            Error::<Test>::UriTooLong
+        );
+
+        UriTooLongPolicy::set(UriPolicy::Truncate);
+        assert_ok!(Multitoken::set_uri(RuntimeOrigin::signed(1), 0, uri.clone()));
+        let stored = BoundedVec::truncate_from(uri[..16].to_vec());
+        assert_eq!(Multitoken::collection_uri(0), Some(stored.clone()));
+        System::assert_last_event(Event::UriSet { id: 0, uri: stored }.into());
+    });
+}
+
+#[test]
+fn test_force_clear_balances() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        for holder in 2..7 {
+            assert_ok!(Multitoken::mint(RuntimeOrigin::signed(1), holder, 0, 10));
+        }
+        let tax = TransferTax {
+            owner_bps: 100,
+            treasury_bps: 0,
+        };
+        assert_ok!(Multitoken::set_transfer_tax(RuntimeOrigin::signed(1), 0, tax));
+
+        assert_noop!(
+            Multitoken::force_clear_balances(RuntimeOrigin::signed(1), 0, 2),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Multitoken::force_clear_balances(RuntimeOrigin::root(), 2, 2),
+            Error::<Test>::CollectionDoesNotExist
+        );
+        assert_noop!(
+            Multitoken::force_clear_balances(RuntimeOrigin::root(), 0, 0),
+            Error::<Test>::ZeroLimit
+        );
+        assert_ok!(Multitoken::start_destroy(RuntimeOrigin::signed(1), 1));
+        assert_noop!(
+            Multitoken::force_clear_balances(RuntimeOrigin::root(), 1, 2),
+            Error::<Test>::Destroying
+        );
+
+        // The cleared balances are burned.
+        assert_ok!(Multitoken::force_clear_balances(RuntimeOrigin::root(), 0, 2));
+        assert_eq!(Balances::<Test>::iter_prefix(0).count(), 3);
+        assert_ok!(Multitoken::force_clear_balances(RuntimeOrigin::root(), 0, 2));
+        assert_eq!(Balances::<Test>::iter_prefix(0).count(), 1);
+        assert_eq!(Multitoken::total_supply(0), 10);
+        assert_eq!(Multitoken::burned(0), 40);
+        assert!(!System::events()
+            .iter()
+            .any(|record| record.event == Event::CollectionCleared { id: 0 }.into()));
+
+        assert_ok!(Multitoken::force_clear_balances(RuntimeOrigin::root(), 0, 2));
+        assert_eq!(Balances::<Test>::iter_prefix(0).count(), 0);
+        let progress: Vec<_> = System::events()
+            .into_iter()
+            .filter_map(|record| match record.event {
+                RuntimeEvent::Multitoken(Event::BatchProgress {
+                    operation: BatchOp::ClearBalances,
+                    id: 0,
+                    processed,
+                    remaining,
+                }) => Some((processed, remaining)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(progress, vec![(2, 2), (2, 1), (1, 0)]);
+        assert_eq!(Multitoken::total_supply(0), 0);
+        assert_eq!(Multitoken::burned(0), 50);
+        assert_eq!(Multitoken::collection_owner(&0), Some(1));
+        assert_eq!(
+            Multitoken::transfer_tax(0),
+            Some(TransferTax {
+                owner_bps: 100,
+                treasury_bps: 0
+            })
+        );
+        System::assert_last_event(Event::CollectionCleared { id: 0 }.into());
+        assert_events_match_balances();
+    });
+}
+
+#[test]
+fn test_mint_to_null_account_fails() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        NullAccount::set(Some(0));
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_noop!(Multitoken::mint(owner.clone(), 0, 0, 100), Error::<Test>::MintToNullAccount);
+        assert_noop!(
+            Multitoken::mint_batch(owner.clone(), 0, vec![0], vec![100]),
+            Error::<Test>::MintToNullAccount
+        );
+
+        assert_ok!(Multitoken::mint(owner, 2, 0, 100));
+        assert_eq!(Multitoken::balance_of(&2, &0), 100);
+        assert_eq!(Multitoken::total_supply(0), 100);
+    });
+}
+
+#[test]
+fn test_transfer_tax() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 10_000));
+
+        // Untaxed collections transfer the full amount.
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 1_000));
+        assert_eq!(Multitoken::balance_of(&3, &0), 1_000);
+
+        assert_noop!(
+            Multitoken::set_transfer_tax(
+                owner.clone(),
+                0,
+                TransferTax {
+                    owner_bps: 6_000,
+                    treasury_bps: 4_001
+                }
+            ),
+            Error::<Test>::InvalidTransferTax
+        );
+        assert_noop!(
+            Multitoken::set_transfer_tax(
+                RuntimeOrigin::signed(2),
+                0,
+                TransferTax {
+                    owner_bps: 1,
+                    treasury_bps: 1
+                }
+            ),
+            Error::<Test>::InvalidOwner
+        );
+        let tax = TransferTax {
+            owner_bps: 250,
+            treasury_bps: 100,
+        };
+        assert_ok!(Multitoken::set_transfer_tax(owner.clone(), 0, tax));
+        System::assert_last_event(Event::TransferTaxSet { id: 0, tax }.into());
+
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 4, 0, 999));
+        // 2.5% and 1% of 999, rounded down.
+        assert_eq!(Multitoken::balance_of(&1, &0), 24);
+        assert_eq!(Multitoken::balance_of(&99, &0), 9);
+        assert_eq!(Multitoken::balance_of(&4, &0), 966);
+        assert_eq!(Multitoken::balance_of(&2, &0), 8_001);
+        assert_eq!(Multitoken::total_supply(0), 10_000);
+        System::assert_has_event(
+            Event::TransferTaxPaid {
+                id: 0,
+                payer: 2,
+                beneficiary: 1,
+                amount: 24,
+            }
+            .into(),
+        );
+        System::assert_last_event(
+            Event::TransferTaxPaid {
+                id: 0,
+                payer: 2,
+                beneficiary: 99,
+                amount: 9,
+            }
+            .into(),
+        );
+
+        // The owner keeps its own share.
+        assert_ok!(Multitoken::mint(owner.clone(), 1, 0, 1_000));
+        assert_ok!(Multitoken::transfer(owner.clone(), 5, 0, 1_000));
+        assert_eq!(Multitoken::balance_of(&5, &0), 965);
+        assert_eq!(Multitoken::balance_of(&1, &0), 49);
+        assert_eq!(Multitoken::balance_of(&99, &0), 19);
+
+        assert_ok!(Multitoken::set_transfer_tax(owner, 0, TransferTax::default()));
+        assert_eq!(Multitoken::transfer_tax(0), None);
+        assert_events_match_balances();
+    });
+}
+
+#[test]
+fn test_supply_breakdown() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_eq!(Multitoken::supply_breakdown(&0), (0, 0, 0));
+
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 100));
+        assert_ok!(Multitoken::mint(owner, 2, 1, 50));
+        assert_eq!(Multitoken::supply_breakdown(&0), (100, 0, 0));
+
+        assert_ok!(Multitoken::burn(RuntimeOrigin::signed(2), 0, 30));
+        assert_ok!(Multitoken::burn_batch(RuntimeOrigin::signed(2), vec![0, 1, 0], vec![5, 10, 5]));
+        assert_eq!(Multitoken::supply_breakdown(&0), (60, 0, 40));
+        assert_eq!(Multitoken::supply_breakdown(&1), (40, 0, 10));
+
+        // Transfers do not burn anything.
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 60));
+        assert_eq!(Multitoken::supply_breakdown(&0), (60, 0, 40));
+    });
+}
+
+#[test]
+fn test_create_deterministic() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let salt = [1u8; 32];
+        assert_noop!(
+            Multitoken::create_deterministic(RuntimeOrigin::signed(1), salt),
+            Error::<Test>::CustomIdsDisabled
+        );
+
+        CustomCollectionIds::set(true);
+        let id = Multitoken::deterministic_collection_id(&1, &salt).unwrap();
+        assert_eq!(Multitoken::deterministic_collection_id(&1, &salt), Ok(id));
+        assert_ne!(Multitoken::deterministic_collection_id(&2, &salt), Ok(id));
+        assert_ne!(Multitoken::deterministic_collection_id(&1, &[2u8; 32]), Ok(id));
+
+        assert_ok!(Multitoken::create_deterministic(RuntimeOrigin::signed(1), salt));
+        System::assert_last_event(Event::CollectionCreated { id, owner: 1 }.into());
+        assert_eq!(Multitoken::collection_owner(&id), Some(1));
+        assert_noop!(
+            Multitoken::create_deterministic(RuntimeOrigin::signed(1), salt),
+            Error::<Test>::CollectionAlreadyExists
+        );
+
+        assert_ok!(Multitoken::create_deterministic(RuntimeOrigin::signed(2), salt));
+        let id = Multitoken::deterministic_collection_id(&2, &salt).unwrap();
+        assert_eq!(Multitoken::collection_owner(&id), Some(2));
+    });
+}
+
+#[test]
+fn test_next_for_integers() {
+    fn check<T: Next + Copy + PartialEq + core::fmt::Debug>(
+        zero: T,
+        one: T,
+        before_max: T,
+        max: T,
+    ) {
+        assert_eq!(zero.next(), one);
+        assert_eq!(zero.checked_next(), Some(one));
+        assert_eq!(before_max.next(), max);
+        assert_eq!(before_max.checked_next(), Some(max));
+        assert_eq!(before_max.remaining(), Some(1));
+        assert_eq!(max.next(), max);
+        assert_eq!(max.checked_next(), None);
+        assert_eq!(max.remaining(), Some(0));
+    }
+    check(0u8, 1, u8::MAX - 1, u8::MAX);
+    check(0u16, 1, u16::MAX - 1, u16::MAX);
+    check(0u32, 1, u32::MAX - 1, u32::MAX);
+    check(0u64, 1, u64::MAX - 1, u64::MAX);
+    check(0u128, 1, u128::MAX - 1, u128::MAX);
+    assert_eq!(0u8.remaining(), Some(255));
+    assert_eq!(0u128.remaining(), Some(u128::MAX));
+}
+
+#[test]
+fn test_set_royalty_recipient() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_noop!(
+            Multitoken::set_royalty_recipient(owner.clone(), 0, 3),
+            Error::<Test>::NoRoyaltySet
+        );
+        assert_noop!(
+            Multitoken::set_royalty(owner.clone(), 0, 2, 10_001),
+            Error::<Test>::RoyaltyTooHigh
+        );
+
+        assert_ok!(Multitoken::set_royalty(owner.clone(), 0, 2, 750));
+        assert_noop!(
+            Multitoken::set_royalty_recipient(RuntimeOrigin::signed(2), 0, 3),
+            Error::<Test>::InvalidOwner
+        );
+        assert_ok!(Multitoken::set_royalty_recipient(owner.clone(), 0, 3));
+        System::assert_last_event(
+            Event::RoyaltyRecipientChanged {
+                id: 0,
+                recipient: 3,
+            }
+            .into(),
+        );
+        assert_eq!(
+            Multitoken::royalty(0),
+            Some(RoyaltyInfo {
+                recipient: 3,
+                bps: 750
+            })
+        );
+
+        assert_ok!(Multitoken::set_royalty(owner.clone(), 0, 3, 0));
+        assert_eq!(Multitoken::royalty(0), None);
+        assert_noop!(Multitoken::set_royalty_recipient(owner, 0, 2), Error::<Test>::NoRoyaltySet);
+    });
+}
+
+#[test]
+fn test_all_exist() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert!(Multitoken::all_exist(&[0, 1, 0]));
+        assert!(!Multitoken::all_exist(&[0, 2, 1]));
+        assert!(Multitoken::all_exist(&[]));
+    });
+}
+
+#[test]
+fn test_transfer_event_topics() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint_batch(owner, 2, vec![0, 1, 0], vec![10, 10, 10]));
+        let last_topics = || System::events().last().unwrap().topics.clone();
+        let topics = last_topics();
+        assert_eq!(topics, Multitoken::transfer_topics(&[0, 1], &[&1, &2]));
+        assert_eq!(topics.len(), 4);
+
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 1, 5));
+        let topics = last_topics();
+        assert_eq!(topics, Multitoken::transfer_topics(&[1], &[&2, &3]));
+        assert_eq!(topics.len(), 3);
+        assert_ne!(topics, Multitoken::transfer_topics(&[0], &[&2, &3]));
+
+        TransferEventTopics::set(false);
+        assert_ok!(Multitoken::burn(RuntimeOrigin::signed(2), 0, 5));
+        assert!(last_topics().is_empty());
+    });
+}
+
+#[test]
+fn test_execute_sale() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (seller, buyer, marketplace, artist) = (1, 2, 3, 4);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(seller), false));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(seller), true));
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(seller), seller, 0, 1));
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(seller), buyer, 1, 1_000));
+        assert_ok!(Multitoken::set_royalty(RuntimeOrigin::signed(seller), 0, artist, 1_000));
+
+        assert_noop!(
+            Multitoken::execute_sale(
+                RuntimeOrigin::signed(marketplace),
+                seller,
+                buyer,
+                0,
+                1,
+                1,
+                500
+            ),
+            Error::<Test>::InsufficientApprovalForAll
+        );
+        assert_ok!(Multitoken::set_approval_for_all(
+            RuntimeOrigin::signed(seller),
+            marketplace,
+            true,
+            None
+        ));
+        assert_noop!(
+            Multitoken::execute_sale(
+                RuntimeOrigin::signed(marketplace),
+                seller,
+                buyer,
+                0,
+                1,
+                1,
+                500
+            ),
+            Error::<Test>::InsufficientApprovalForAll
+        );
+        assert_ok!(Multitoken::set_approval_for_all(
+            RuntimeOrigin::signed(buyer),
+            marketplace,
+            true,
+            None
+        ));
+
+        // Either leg failing rolls back the whole sale.
+        assert_noop!(
+            Multitoken::execute_sale(
+                RuntimeOrigin::signed(marketplace),
+                seller,
+                buyer,
+                0,
+                1,
+                1,
+                1_001
+            ),
+            Error::<Test>::InsufficientBalance
+        );
+        assert_noop!(
+            Multitoken::execute_sale(
+                RuntimeOrigin::signed(marketplace),
+                seller,
+                buyer,
+                0,
+                2,
+                1,
+                500
+            ),
+            Error::<Test>::InsufficientBalance
+        );
+
+        assert_ok!(Multitoken::execute_sale(
+            RuntimeOrigin::signed(marketplace),
+            seller,
+            buyer,
+            0,
+            1,
+            1,
+            500
+        ));
+        assert_eq!(Multitoken::balance_of(&buyer, &0), 1);
+        assert_eq!(Multitoken::balance_of(&seller, &0), 0);
+        assert_eq!(Multitoken::balance_of(&artist, &1), 50);
+        assert_eq!(Multitoken::balance_of(&seller, &1), 450);
+        assert_eq!(Multitoken::balance_of(&buyer, &1), 500);
+        System::assert_last_event(
+            Event::SaleExecuted {
+                seller,
+                buyer,
+                item_id: 0,
+                item_amount: 1,
+                payment_id: 1,
+                payment_amount: 500,
+                royalty: 50,
+            }
+            .into(),
+        );
+        assert_events_match_balances();
+    });
+}
+
+#[test]
+fn test_last_collection_id() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_eq!(Multitoken::last_collection_id(), None);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_eq!(Multitoken::last_collection_id(), Some(0));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), false));
+        assert_eq!(Multitoken::last_collection_id(), Some(2));
+        assert_eq!(Multitoken::next_collection_id(), 3);
+
+        CustomCollectionIds::set(true);
+        assert_ok!(Multitoken::ensure_collection_and_mint(owner, 7, 2, 10));
+        assert_eq!(Multitoken::last_collection_id(), Some(7));
+    });
+}
+
+#[test]
+fn test_inflation_schedule() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 1, 0, 10));
+        assert_noop!(
+            Multitoken::set_max_supply(owner.clone(), 0, Some(9)),
+            Error::<Test>::MaxSupplyExceeded
+        );
+        assert_ok!(Multitoken::set_max_supply(owner.clone(), 0, Some(35)));
+        assert_noop!(Multitoken::mint(owner.clone(), 1, 0, 26), Error::<Test>::MaxSupplyExceeded);
+        assert_noop!(
+            Multitoken::set_inflation_schedule(RuntimeOrigin::signed(2), 0, Some((10, 2))),
+            Error::<Test>::InvalidOwner
+        );
+
+        assert_ok!(Multitoken::set_inflation_schedule(owner, 0, Some((10, 2))));
+        run_to_block(2);
+        assert_eq!(Multitoken::balance_of(&2, &0), 10);
+        run_to_block(3);
+        assert_eq!(Multitoken::balance_of(&2, &0), 20);
+        // Only the remaining 5 tokens fit under the cap.
+        run_to_block(5);
+        assert_eq!(Multitoken::balance_of(&2, &0), 25);
+        assert_eq!(Multitoken::total_supply(0), 35);
+        assert_eq!(Multitoken::inflation_schedule(0), None);
+        assert_events_match_balances();
+    });
+}
+
+#[test]
+fn test_inflation_is_bounded_per_block() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        MaxInflationsPerBlock::set(2);
+        let owner = RuntimeOrigin::signed(1);
+        for id in 0..3 {
+            assert_ok!(Multitoken::create(owner.clone(), true));
+            assert_ok!(Multitoken::set_inflation_schedule(owner.clone(), id, Some((1, 2))));
+        }
+        let minted = || {
+            (0..3)
+                .map(|id| Multitoken::balance_of(&2, &id))
+                .sum::<u64>()
+        };
+
+        run_to_block(2);
+        assert_eq!(minted(), 2);
+        // The schedule left out of block 2 goes first in block 3.
+        run_to_block(3);
+        assert_eq!(minted(), 3);
+        run_to_block(5);
+        assert_eq!(minted(), 6);
+        assert!((0..3).all(|id| Multitoken::balance_of(&2, &id) == 2));
+
+        // Hitting the limit exactly at the last schedule starts over in the next block.
+        MaxInflationsPerBlock::set(3);
+        run_to_block(6);
+        assert_eq!(minted(), 9);
+        run_to_block(7);
+        assert_eq!(minted(), 12);
+    });
+}
+
+#[test]
+fn test_transfers_are_logged() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        captured_logs();
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(1), 2, 0, 100));
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 40));
+        assert_ok!(Multitoken::burn(RuntimeOrigin::signed(3), 0, 15));
+        assert_eq!(
+            captured_logs(),
+            vec![
+                "minted 100 of collection 0 to 2".to_string(),
+                "transferred 40 of collection 0 from 2 to 3".to_string(),
+                "burned 15 of collection 0 from 3".to_string(),
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_create_filter() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        DeniedCreator::set(Some(2));
+        assert_noop!(
+            Multitoken::create(RuntimeOrigin::signed(2), true),
+            Error::<Test>::CreationNotPermitted
+        );
+        CustomCollectionIds::set(true);
+        assert_noop!(
+            Multitoken::ensure_collection_and_mint(RuntimeOrigin::signed(2), 5, 2, 10),
+            Error::<Test>::CreationNotPermitted
+        );
+
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert_eq!(Multitoken::collection_owner(&0), Some(1));
+    });
+}
+
+#[test]
+fn test_safe_transfer_from_with_memo() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner, 2, 0, 100));
+        type Memo = BoundedVec<u8, <Test as crate::Config>::MaxMemoLength>;
+        let memo = Memo::truncate_from(b"invoice 42".to_vec());
+
+        assert_ok!(Multitoken::safe_transfer_from_with_memo(
+            RuntimeOrigin::signed(2),
+            2,
+            3,
+            0,
+            40,
+            memo.clone()
+        ));
+        let events = System::events();
+        assert_eq!(
+            events[events.len() - 2].event,
+            Event::TransferSingle {
+                operator: 2,
+                from: Some(2),
+                to: Some(3),
+                id: 0,
+                value: 40,
+                fungible: true,
+            }
+            .into()
+        );
+        System::assert_last_event(
+            Event::TransferMemo {
+                id: 0,
+                memo: memo.clone(),
+            }
+            .into(),
+        );
+        assert_eq!(Multitoken::balance_of(&2, &0), 60);
+        assert_eq!(Multitoken::balance_of(&3, &0), 40);
+
+        assert_noop!(
+            Multitoken::safe_transfer_from_with_memo(RuntimeOrigin::signed(3), 2, 3, 0, 10, memo),
+            Error::<Test>::InsufficientApprovalForAll
+        );
+        let max = <Test as crate::Config>::MaxMemoLength::get() as usize;
+        assert!(Memo::try_from(vec![0u8; max]).is_ok());
+        assert!(Memo::try_from(vec![0u8; max + 1]).is_err());
+    });
+}
+
+#[test]
+fn test_balances_of_account_by_creator() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (studio, other, player) = (1, 2, 3);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(studio), true));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(other), true));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(studio), false));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(studio), true));
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(studio), player, 0, 10));
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(other), player, 1, 20));
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(studio), player, 2, 1));
+
+        let mut holdings = Multitoken::balances_of_account_by_creator(&player, &studio);
+        holdings.sort();
+        assert_eq!(holdings, vec![(0, 10), (2, 1)]);
+        assert_eq!(Multitoken::balances_of_account_by_creator(&player, &other), vec![(1, 20)]);
+        assert_eq!(Multitoken::balances_of_account_by_creator(&studio, &studio), vec![]);
+    });
+}
+
+#[test]
+fn test_balance_of_batch() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(1), 2, 0, 100));
+        assert_eq!(Multitoken::balance_of_batch(&vec![], &vec![]), Some(vec![]));
+        assert_eq!(Multitoken::balance_of_batch(&vec![2, 3], &vec![0, 0]), Some(vec![100, 0]));
+        assert_eq!(Multitoken::balance_of_batch(&vec![2], &vec![]), None);
+        assert_eq!(Multitoken::balance_of_batch(&vec![2], &vec![0, 0]), None);
+    });
+}
+
+#[test]
+fn test_is_fungible_collection() {
+    use frame_support::traits::tokens::{fungibles::Inspect, DepositConsequence, Provenance};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), false));
+        assert!(Multitoken::is_fungible_collection(&0));
+        assert!(!Multitoken::is_fungible_collection(&1));
+        assert!(!Multitoken::is_fungible_collection(&2));
+
+        // Only fungible collections are exposed as assets.
+        assert!(<Multitoken as Inspect<u64>>::asset_exists(0));
+        assert!(!<Multitoken as Inspect<u64>>::asset_exists(1));
+        assert!(!<Multitoken as Inspect<u64>>::asset_exists(2));
+        assert_eq!(
+            <Multitoken as Inspect<u64>>::can_deposit(0, &2, 1, Provenance::Minted),
+            DepositConsequence::Success
+        );
+        for id in [1, 2] {
+            assert_eq!(
+                <Multitoken as Inspect<u64>>::can_deposit(id, &2, 1, Provenance::Minted),
+                DepositConsequence::UnknownAsset
+            );
+        }
+    });
+}
+
+#[test]
+fn test_recent_transfers() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 100));
+        assert!(Multitoken::recent_transfers(0).is_empty());
+
+        KeepTransferHistory::set(true);
+        assert_ok!(Multitoken::mint(owner, 2, 0, 100));
+        System::set_block_number(2);
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 10));
+        assert_ok!(Multitoken::burn(RuntimeOrigin::signed(3), 0, 5));
+        assert_eq!(
+            Multitoken::recent_transfers(0).into_inner(),
+            vec![
+                (None, Some(2), 100, 1),
+                (Some(2), Some(3), 10, 2),
+                (Some(3), None, 5, 2)
+            ]
+        );
+
+        System::set_block_number(3);
+        assert_ok!(Multitoken::safe_batch_transfer_from(
+            RuntimeOrigin::signed(2),
+            2,
+            4,
+            vec![0, 0],
+            vec![1, 2]
+        ));
+        assert_eq!(
+            Multitoken::recent_transfers(0).into_inner(),
+            vec![
+                (Some(3), None, 5, 2),
+                (Some(2), Some(4), 1, 3),
+                (Some(2), Some(4), 2, 3)
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_repeated_ids_accumulate_within_a_batch() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint_batch(owner, 2, vec![0, 0], vec![10, 5]));
+        assert_eq!(Multitoken::balance_of(&2, &0), 15);
+        assert_eq!(Multitoken::total_supply(0), 15);
+
+        assert_noop!(
+            Multitoken::safe_batch_transfer_from(
+                RuntimeOrigin::signed(2),
+                2,
+                3,
+                vec![0, 0],
+                vec![10, 6]
+            ),
+            Error::<Test>::InsufficientBalanceInBatch { index: 1 }
+        );
+        assert_ok!(Multitoken::safe_batch_transfer_from(
+            RuntimeOrigin::signed(2),
+            2,
+            3,
+            vec![0, 0],
+            vec![10, 5]
+        ));
+        assert_eq!(Multitoken::balance_of(&2, &0), 0);
+        assert_eq!(Multitoken::balance_of(&3, &0), 15);
+        assert_eq!(Multitoken::total_supply(0), 15);
+        assert_events_match_balances();
+    });
+}
+
+#[test]
+fn test_mint_consent() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 10));
+        assert_ok!(Multitoken::mint(owner.clone(), 1, 0, 10));
+
+        RequireMintConsent::set(true);
+        assert_noop!(Multitoken::mint(owner.clone(), 2, 0, 10), Error::<Test>::MintConsentRequired);
+        assert_noop!(
+            Multitoken::mint_batch(owner.clone(), 2, vec![0], vec![10]),
+            Error::<Test>::MintConsentRequired
+        );
+        assert_ok!(Multitoken::mint(owner.clone(), 1, 0, 10));
+
+        // Approving the minter as an operator is not a consent to its mints.
+        assert_ok!(Multitoken::set_approval_for_all(RuntimeOrigin::signed(2), 1, true, None));
+        assert_noop!(Multitoken::mint(owner.clone(), 2, 0, 10), Error::<Test>::MintConsentRequired);
+
+        assert_ok!(Multitoken::set_mint_consent(RuntimeOrigin::signed(2), 1, true));
+        System::assert_last_event(
+            Event::MintConsentSet {
+                account: 2,
+                minter: 1,
+                consent: true,
+            }
+            .into(),
+        );
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 10));
+        assert_eq!(Multitoken::balance_of(&2, &0), 20);
+        assert_eq!(Multitoken::balance_of(&1, &0), 20);
+
+        assert_ok!(Multitoken::set_mint_consent(RuntimeOrigin::signed(2), 1, false));
+        assert_noop!(Multitoken::mint(owner, 2, 0, 10), Error::<Test>::MintConsentRequired);
+    });
+}
+
+#[test]
+fn test_create_collection_returns_the_assigned_id() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        CustomCollectionIds::set(true);
+        assert_ok!(Multitoken::ensure_collection_and_mint(RuntimeOrigin::signed(1), 0, 2, 10));
+        assert_eq!(Multitoken::next_collection_id(), 0);
+
+        // The helper skips the id taken above, so `NextCollectionId` read before creating would
+        // be wrong.
+        assert_eq!(Multitoken::create_collection(3, true), Ok(1));
+        System::assert_last_event(Event::CollectionCreated { id: 1, owner: 3 }.into());
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(3), false));
+        System::assert_last_event(Event::CollectionCreated { id: 2, owner: 3 }.into());
+        assert_eq!(Multitoken::collection_owner(&0), Some(1));
+        assert_eq!(Multitoken::balance_of(&2, &0), 10);
+    });
+}
+
+#[test]
+fn test_self_approvals_are_rejected() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::allow_claim(owner, 0, 2, 10));
+        let account = RuntimeOrigin::signed(2);
+        assert_noop!(
+            Multitoken::set_approval_for_all(account.clone(), 2, true, None),
+            Error::<Test>::InvalidOperator
+        );
+        assert_noop!(
+            Multitoken::set_capped_approval(account.clone(), 2, 10),
+            Error::<Test>::InvalidOperator
+        );
+        assert_noop!(Multitoken::claim_and_approve(account, 0, 2), Error::<Test>::InvalidOperator);
+        assert!(OperatorApprovals::<Test>::get(2, 2).is_none());
+    });
+}
+
+#[test]
+fn test_has_holders() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert!(!Multitoken::has_holders(&0));
+
+        assert_ok!(Multitoken::mint(owner, 2, 0, 10));
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 4));
+        assert!(Multitoken::has_holders(&0));
+        assert!(!Multitoken::has_holders(&1));
+
+        assert_ok!(Multitoken::burn(RuntimeOrigin::signed(2), 0, 6));
+        assert!(Multitoken::has_holders(&0));
+        assert_ok!(Multitoken::burn(RuntimeOrigin::signed(3), 0, 4));
+        assert!(!Multitoken::has_holders(&0));
+    });
+}
+
+#[test]
+fn test_operator_transfer_events() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner, 2, 0, 100));
+        assert_ok!(Multitoken::set_approval_for_all(RuntimeOrigin::signed(2), 3, true, None));
+        let operator_events = || {
+            System::events()
+                .into_iter()
+                .filter(|record| {
+                    matches!(record.event, RuntimeEvent::Multitoken(Event::OperatorTransfer { .. }))
+                })
+                .count()
+        };
+
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(3), 2, 4, 0, 10));
+        assert_eq!(operator_events(), 0);
+
+        EmitOperatorEvents::set(true);
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(2), 2, 4, 0, 10));
+        assert_eq!(operator_events(), 0);
+
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(3), 2, 4, 0, 10));
+        System::assert_last_event(
+            Event::OperatorTransfer {
+                operator: 3,
+                owner: 2,
+                id: 0,
+                amount: 10,
+            }
+            .into(),
+        );
+        assert_eq!(operator_events(), 1);
+        EmitOperatorEvents::set(false);
+    });
+}
+
+#[test]
+fn test_max_creates_for_weight() {
+    new_test_ext().execute_with(|| {
+        let per_create = <() as crate::WeightInfo>::create();
+        assert_eq!(Multitoken::max_creates_for_weight(Weight::zero()), 0);
+        assert_eq!(Multitoken::max_creates_for_weight(per_create), 1);
+        let available = per_create.saturating_mul(7).saturating_add(per_create / 2);
+        let count = Multitoken::max_creates_for_weight(available);
+        assert_eq!(count, 7);
+        assert!(per_create.saturating_mul(count.into()).all_lte(available));
+        assert!(!per_create
+            .saturating_mul((count + 1).into())
+            .all_lte(available));
+    });
+}
+
+#[test]
+fn test_pause_collection() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 100));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 1, 100));
+
+        assert_noop!(
+            Multitoken::pause_collection(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::InvalidOwner
+        );
+        assert_noop!(
+            Multitoken::pause_collection(RuntimeOrigin::root(), 5),
+            Error::<Test>::CollectionDoesNotExist
+        );
+        assert_ok!(Multitoken::pause_collection(owner.clone(), 0));
+        System::assert_last_event(
+            Event::CollectionPauseSet {
+                id: 0,
+                paused: true,
+            }
+            .into(),
+        );
+        assert!(Multitoken::is_frozen(&0, &2));
+        assert!(!Multitoken::is_frozen(&1, &2));
+
+        assert_noop!(
+            Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 10),
+            Error::<Test>::CollectionPaused
+        );
+        assert_noop!(Multitoken::mint(owner.clone(), 2, 0, 10), Error::<Test>::CollectionPaused);
+        assert_noop!(
+            Multitoken::safe_batch_transfer_from(
+                RuntimeOrigin::signed(2),
+                2,
+                3,
+                vec![1, 0],
+                vec![10, 10]
+            ),
+            Error::<Test>::CollectionPaused
+        );
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 1, 10));
+        assert_eq!(Multitoken::balance_of(&3, &1), 10);
+
+        assert_ok!(Multitoken::unpause_collection(RuntimeOrigin::root(), 0));
+        System::assert_last_event(
+            Event::CollectionPauseSet {
+                id: 0,
+                paused: false,
+            }
+            .into(),
+        );
+        assert!(!CollectionPaused::<Test>::contains_key(0));
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 10));
+        assert_eq!(Multitoken::balance_of(&3, &0), 10);
+    });
+}
+
+#[test]
+fn test_transfer_events_encoding_is_stable() {
+    // Variant index followed by the fields that indexers have always decoded. Fields added later
+    // may only follow them.
+    let single = Event::<Test>::TransferSingle {
+        operator: 1,
+        from: Some(2),
+        to: None,
+        id: 3,
+        value: 4,
+        fungible: true,
+    };
+    let mut expected = vec![1u8];
+    expected.extend((1u64, Some(2u64), None::<u64>, 3u64, 4u64).encode());
+    assert_eq!(single.encode()[..expected.len()], expected[..]);
+    assert_eq!(single.encode()[expected.len()..], true.encode()[..]);
+
+    let batch = Event::<Test>::TransferBatch {
+        operator: 1,
+        from: None,
+        to: Some(2),
+        ids: BoundedVec::truncate_from(vec![3, 4]),
+        values: BoundedVec::truncate_from(vec![5, 6]),
+        fungible: BoundedVec::truncate_from(vec![true, false]),
+    };
+    let mut expected = vec![2u8];
+    expected.extend((1u64, None::<u64>, Some(2u64), vec![3u64, 4], vec![5u64, 6]).encode());
+    assert_eq!(batch.encode()[..expected.len()], expected[..]);
+
+    let created = Event::<Test>::CollectionCreated { id: 1, owner: 2 };
+    let mut expected = vec![0u8];
+    expected.extend((1u64, 2u64).encode());
+    assert_eq!(created.encode(), expected);
+
+    let approval = Event::<Test>::ApprovalForAll {
+        account: 1,
+        operator: 2,
+        approved: true,
+    };
+    let mut expected = vec![3u8];
+    expected.extend((1u64, 2u64, true).encode());
+    assert_eq!(approval.encode(), expected);
+}
+
+#[test]
+fn test_orphaned_collections_go_to_the_fallback() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            Multitoken::set_orphan_fallback(RuntimeOrigin::signed(1), Some(9)),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Multitoken::set_orphan_fallback(RuntimeOrigin::root(), Some(9)));
+        System::assert_last_event(Event::OrphanFallbackSet { account: Some(9) }.into());
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(2), true));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(3), true));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(2), false));
+
+        System::inc_providers(&2);
+        assert_ok!(System::dec_providers(&2));
+        // Nothing changes hands until `on_idle` runs.
+        assert_eq!(Multitoken::collection_owner(&0), Some(2));
+
+        Multitoken::on_idle(1, Weight::MAX);
+        assert!(!ReapedOwners::<Test>::contains_key(2));
+        assert_eq!(Multitoken::collection_owner(&0), Some(9));
+        assert_eq!(Multitoken::collection_owner(&1), Some(3));
+        assert_eq!(Multitoken::collection_owner(&2), Some(9));
+        assert_eq!(Multitoken::owner_collection_count(2), 0);
+        assert_eq!(Multitoken::owner_collection_count(9), 2);
+        for id in [0, 2] {
+            System::assert_has_event(Event::OrphanedCollectionReassigned { id, owner: 9 }.into());
+        }
+
+        // Accounts without collections are not queued.
+        System::inc_providers(&4);
+        assert_ok!(System::dec_providers(&4));
+        assert!(!ReapedOwners::<Test>::contains_key(4));
+    });
+}
+
+#[test]
+fn test_orphaned_collections_are_frozen_without_fallback() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(2), true));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(3), true));
+
+        System::inc_providers(&2);
+        assert_ok!(System::dec_providers(&2));
+        Multitoken::on_idle(1, Weight::MAX);
+        assert!(!ReapedOwners::<Test>::contains_key(2));
+        assert_eq!(Multitoken::collection_owner(&0), Some(2));
+        assert!(CollectionsV2::<Test>::get(0).unwrap().frozen);
+        assert!(!CollectionsV2::<Test>::get(1).unwrap().frozen);
+        System::assert_has_event(
+            Event::CollectionFrozen {
+                id: 0,
+                frozen: true,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_owners_reaped_mid_sweep_keep_every_collection() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Multitoken::set_orphan_fallback(RuntimeOrigin::root(), Some(9)));
+        for _ in 0..2 {
+            for owner in 2..5 {
+                assert_ok!(Multitoken::create_collection(owner, true));
+            }
+        }
+
+        let db_weight = <Test as frame_system::Config>::DbWeight::get();
+        // The sweeps before it read three items, then two collections are visited per call.
+        let budget = db_weight
+            .reads(4)
+            .saturating_add(db_weight.reads_writes(2, 1))
+            .saturating_add(db_weight.reads_writes(6, 8));
+        System::inc_providers(&2);
+        assert_ok!(System::dec_providers(&2));
+        Multitoken::on_idle(1, budget);
+        assert_eq!(OrphanSweepCursor::<Test>::get().map(|(owner, _)| owner), Some(2));
+
+        // Owners reaped in the middle of the sweep are handled once it is over.
+        for owner in 3..5 {
+            System::inc_providers(&owner);
+            assert_ok!(System::dec_providers(&owner));
+        }
+        while ReapedOwners::<Test>::iter().next().is_some() {
+            Multitoken::on_idle(1, budget);
+        }
+        assert!(OrphanSweepCursor::<Test>::get().is_none());
+        for id in 0..6 {
+            assert_eq!(Multitoken::collection_owner(&id), Some(9));
+        }
+        assert_eq!(Multitoken::owner_collection_count(9), 6);
+    });
+}
+
+#[test]
+fn test_transferable_balance() {
+    new_test_ext().execute_with(|| {
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 100));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 1, 50));
+        assert_eq!(Multitoken::transferable_balance(&0, &2), 100);
+        assert_eq!(Multitoken::transferable_balance(&0, &3), 0);
+
+        assert_ok!(Multitoken::freeze_account(owner.clone(), 0, 2, true));
+        assert_eq!(Multitoken::transferable_balance(&0, &2), 0);
+        assert_eq!(Multitoken::transferable_balance(&1, &2), 50);
+        assert_ok!(Multitoken::freeze_account(owner.clone(), 0, 2, false));
+
+        assert_ok!(Multitoken::freeze_collection(owner.clone(), 0, true));
+        assert_eq!(Multitoken::transferable_balance(&0, &2), 0);
+        assert_ok!(Multitoken::freeze_collection(owner.clone(), 0, false));
+
+        assert_ok!(Multitoken::pause_collection(owner.clone(), 1));
+        assert_eq!(Multitoken::transferable_balance(&0, &2), 100);
+        assert_eq!(Multitoken::transferable_balance(&1, &2), 0);
+        assert_ok!(Multitoken::unpause_collection(owner, 1));
+
+        assert_ok!(Multitoken::set_paused(RuntimeOrigin::root(), true));
+        assert_eq!(Multitoken::transferable_balance(&0, &2), 0);
+        assert_eq!(Multitoken::transferable_balance(&1, &2), 0);
+        assert_ok!(Multitoken::set_paused(RuntimeOrigin::root(), false));
+
+        assert_ok!(Multitoken::transfer(RuntimeOrigin::signed(2), 3, 0, 30));
+        assert_eq!(Multitoken::transferable_balance(&0, &2), 70);
+    });
+}
+
+#[test]
+fn test_collection_approvals() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 100));
+        assert_ok!(Multitoken::mint(owner, 2, 1, 100));
+        let account = RuntimeOrigin::signed(2);
+        assert_noop!(
+            Multitoken::set_approval_for_collection(account.clone(), 3, 5, true, None),
+            Error::<Test>::CollectionDoesNotExist
+        );
+        assert_noop!(
+            Multitoken::set_approval_for_collection(account.clone(), 2, 0, true, None),
+            Error::<Test>::InvalidOperator
+        );
+
+        assert_ok!(Multitoken::set_approval_for_collection(account.clone(), 3, 0, true, None));
+        System::assert_last_event(
+            Event::CollectionApproval {
+                account: 2,
+                operator: 3,
+                id: 0,
+                approved: true,
+            }
+            .into(),
+        );
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &3, &0, 10),
+            AuthorizationResult::GrantedByCollection
+        );
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &3, &1, 10),
+            AuthorizationResult::DeniedNotApproved
+        );
+        let operator = RuntimeOrigin::signed(3);
+        assert_ok!(Multitoken::safe_batch_transfer_from(
+            operator.clone(),
+            2,
+            4,
+            vec![0, 0],
+            vec![10, 5]
+        ));
+        assert_eq!(Multitoken::balance_of(&4, &0), 15);
+        assert_noop!(
+            Multitoken::safe_batch_transfer_from(operator.clone(), 2, 4, vec![0, 1], vec![1, 1]),
+            Error::<Test>::InsufficientApprovalForAll
+        );
+
+        // The approval for all still covers every collection.
+        assert_ok!(Multitoken::set_approval_for_all(account.clone(), 3, true, None));
+        assert_ok!(Multitoken::safe_batch_transfer_from(
+            operator.clone(),
+            2,
+            4,
+            vec![0, 1],
+            vec![1, 1]
+        ));
+        assert_ok!(Multitoken::set_approval_for_all(account.clone(), 3, false, None));
+
+        assert_ok!(Multitoken::set_approval_for_collection(account, 3, 0, false, None));
+        assert!(!CollectionApprovals::<Test>::contains_key(2, (0, 3)));
+        assert_noop!(
+            Multitoken::safe_transfer_from(operator, 2, 4, 0, 1),
+            Error::<Test>::InsufficientApprovalForAll
+        );
+    });
+}
+
+#[test]
+fn test_allowances() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 100));
+        assert_ok!(Multitoken::mint(owner, 2, 1, 100));
+        let account = RuntimeOrigin::signed(2);
+        assert_noop!(
+            Multitoken::approve(account.clone(), 2, 0, 10, None),
+            Error::<Test>::InvalidOperator
+        );
+        assert_noop!(
+            Multitoken::approve(account.clone(), 3, 5, 10, None),
+            Error::<Test>::CollectionDoesNotExist
+        );
+
+        assert_ok!(Multitoken::approve(account.clone(), 3, 0, 30, None));
+        System::assert_last_event(
+            Event::Approval {
+                owner: 2,
+                operator: 3,
+                id: 0,
+                amount: 30,
+            }
+            .into(),
+        );
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &3, &0, 30),
+            AuthorizationResult::GrantedByAllowance { remaining: 30 }
+        );
+        let operator = RuntimeOrigin::signed(3);
+        assert_ok!(Multitoken::safe_batch_transfer_from(
+            operator.clone(),
+            2,
+            4,
+            vec![0, 0],
+            vec![10, 5]
+        ));
+        assert_eq!(Allowances::<Test>::get(2, (0, 3)), Some((15, None)));
+        assert_noop!(
+            Multitoken::safe_transfer_from(operator.clone(), 2, 4, 0, 16),
+            Error::<Test>::AllowanceExceeded
+        );
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &3, &0, 16),
+            AuthorizationResult::DeniedInsufficientAllowance { remaining: 15 }
+        );
+        assert_noop!(
+            Multitoken::safe_batch_transfer_from(operator.clone(), 2, 4, vec![0, 1], vec![1, 1]),
+            Error::<Test>::InsufficientApprovalForAll
+        );
+
+        assert_ok!(Multitoken::safe_transfer_from(operator.clone(), 2, 4, 0, 15));
+        assert_eq!(Multitoken::balance_of(&4, &0), 30);
+        assert!(!Allowances::<Test>::contains_key(2, (0, 3)));
+
+        assert_ok!(Multitoken::approve(account.clone(), 3, 1, 10, None));
+        assert_ok!(Multitoken::approve(account, 3, 1, 0, None));
+        assert!(!Allowances::<Test>::contains_key(2, (1, 3)));
+        assert_noop!(
+            Multitoken::safe_transfer_from(operator, 2, 4, 1, 1),
+            Error::<Test>::InsufficientApprovalForAll
+        );
+    });
+}
+
+#[test]
+fn test_approval_deadlines() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner.clone(), 2, 0, 100));
+        assert_ok!(Multitoken::mint(owner, 2, 1, 100));
+        let account = RuntimeOrigin::signed(2);
+        assert_ok!(Multitoken::set_approval_for_all(account.clone(), 3, true, Some(2)));
+        assert_ok!(Multitoken::set_approval_for_collection(account.clone(), 4, 0, true, Some(3)));
+        assert_ok!(Multitoken::approve(account, 4, 1, 50, Some(3)));
+
+        System::set_block_number(2);
+        assert!(Multitoken::is_approved_for_all(&2, &3));
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(3), 2, 5, 0, 1));
+        assert_ok!(Multitoken::safe_batch_transfer_from(
+            RuntimeOrigin::signed(4),
+            2,
+            5,
+            vec![0, 1],
+            vec![1, 1]
+        ));
+
+        System::set_block_number(3);
+        assert!(!Multitoken::is_approved_for_all(&2, &3));
+        assert_noop!(
+            Multitoken::safe_transfer_from(RuntimeOrigin::signed(3), 2, 5, 0, 1),
+            Error::<Test>::ApprovalExpired
+        );
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(4), 2, 5, 1, 1));
+
+        System::set_block_number(4);
+        assert_noop!(
+            Multitoken::safe_transfer_from(RuntimeOrigin::signed(4), 2, 5, 0, 1),
+            Error::<Test>::ApprovalExpired
+        );
+        assert_noop!(
+            Multitoken::safe_transfer_from(RuntimeOrigin::signed(4), 2, 5, 1, 1),
+            Error::<Test>::ApprovalExpired
+        );
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &4, &1, 1),
+            AuthorizationResult::DeniedExpired
+        );
+        assert_eq!(Multitoken::balance_of(&5, &0), 2);
+        assert_eq!(Multitoken::balance_of(&5, &1), 2);
+    });
+}
+
+#[test]
+fn test_clear_all_approvals() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        let account = RuntimeOrigin::signed(2);
+        for operator in 3..6 {
+            assert_ok!(Multitoken::set_approval_for_all(account.clone(), operator, true, None));
+        }
+        assert_ok!(Multitoken::set_capped_approval(account.clone(), 6, 10));
+        assert_ok!(Multitoken::set_approval_for_collection(account.clone(), 3, 0, true, None));
+        assert_ok!(Multitoken::approve(account.clone(), 4, 0, 10, None));
+        assert_ok!(Multitoken::set_approval_for_all(RuntimeOrigin::signed(7), 3, true, None));
+
+        // At most `MaxApprovalsCleared` are removed per call.
+        let result = Multitoken::clear_all_approvals(account.clone());
+        assert_ok!(result);
+        assert_eq!(
+            result.unwrap().actual_weight,
+            Some(<() as crate::WeightInfo>::clear_all_approvals(4))
+        );
+        System::assert_last_event(
+            Event::AllApprovalsCleared {
+                account: 2,
+                removed: 4,
+                complete: false,
+            }
+            .into(),
+        );
+        assert!(OperatorApprovals::<Test>::iter_prefix(2).next().is_none());
+        assert!(ApprovalsByOperator::<Test>::get(3, 2).is_none());
+        assert!(Multitoken::approval_cap(2, 6).is_none());
+
+        assert_ok!(Multitoken::clear_all_approvals(account.clone()));
+        System::assert_last_event(
+            Event::AllApprovalsCleared {
+                account: 2,
+                removed: 2,
+                complete: true,
+            }
+            .into(),
+        );
+        assert!(CollectionApprovals::<Test>::iter_prefix(2).next().is_none());
+        assert!(Allowances::<Test>::iter_prefix(2).next().is_none());
+        assert!(Multitoken::is_approved_for_all(&7, &3));
+
+        assert_ok!(Multitoken::clear_all_approvals(account));
+        System::assert_last_event(
+            Event::AllApprovalsCleared {
+                account: 2,
+                removed: 0,
+                complete: true,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn test_increase_and_decrease_allowance() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner, 2, 0, 100));
+        let account = RuntimeOrigin::signed(2);
+        assert_noop!(
+            Multitoken::increase_allowance(account.clone(), 3, 5, 10),
+            Error::<Test>::CollectionDoesNotExist
+        );
+        assert_noop!(
+            Multitoken::decrease_allowance(account.clone(), 3, 0, 1),
+            ArithmeticError::Underflow
+        );
+
+        assert_ok!(Multitoken::increase_allowance(account.clone(), 3, 0, 10));
+        assert_eq!(Allowances::<Test>::get(2, (0, 3)), Some((10, None)));
+        assert_ok!(Multitoken::approve(account.clone(), 3, 0, 20, Some(9)));
+        assert_ok!(Multitoken::increase_allowance(account.clone(), 3, 0, 15));
+        System::assert_last_event(
+            Event::Approval {
+                owner: 2,
+                operator: 3,
+                id: 0,
+                amount: 35,
+            }
+            .into(),
+        );
+        assert_eq!(Allowances::<Test>::get(2, (0, 3)), Some((35, Some(9))));
+        assert_noop!(
+            Multitoken::increase_allowance(account.clone(), 3, 0, u64::MAX),
+            ArithmeticError::Overflow
+        );
+
+        // Spending in between is not overwritten, unlike a second `approve`.
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(3), 2, 4, 0, 30));
+        assert_noop!(
+            Multitoken::decrease_allowance(account.clone(), 3, 0, 10),
+            ArithmeticError::Underflow
+        );
+        assert_ok!(Multitoken::decrease_allowance(account.clone(), 3, 0, 2));
+        assert_eq!(Allowances::<Test>::get(2, (0, 3)), Some((3, Some(9))));
+        assert_ok!(Multitoken::decrease_allowance(account.clone(), 3, 0, 3));
+        System::assert_last_event(
+            Event::Approval {
+                owner: 2,
+                operator: 3,
+                id: 0,
+                amount: 0,
+            }
+            .into(),
+        );
+        assert!(!Allowances::<Test>::contains_key(2, (0, 3)));
+
+        // An expired allowance is not revived with its old deadline.
+        assert_ok!(Multitoken::approve(account.clone(), 3, 0, 5, Some(9)));
+        System::set_block_number(10);
+        assert_noop!(
+            Multitoken::increase_allowance(account, 3, 0, 10),
+            Error::<Test>::ApprovalExpired
+        );
+    });
+}
+
+#[test]
+fn test_permit_approval() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let relayer = RuntimeOrigin::signed(5);
+        let payload = Multitoken::permit_payload(&2, &3, true, 0, 2);
+
+        // Signatures over the bare fields are not valid permits.
+        let undomained = TestSignature(2, (2u64, 3u64, true, 0u64, 2u64).encode());
+        assert_noop!(
+            Multitoken::permit_approval(relayer.clone(), 2, 3, true, 2, undomained),
+            Error::<Test>::InvalidSignature
+        );
+
+        let forged = TestSignature(4, payload.clone());
+        assert_noop!(
+            Multitoken::permit_approval(relayer.clone(), 2, 3, true, 2, forged),
+            Error::<Test>::InvalidSignature
+        );
+        let tampered = TestSignature(2, payload.clone());
+        assert_noop!(
+            Multitoken::permit_approval(relayer.clone(), 2, 4, true, 2, tampered),
+            Error::<Test>::InvalidSignature
+        );
+
+        let signature = TestSignature(2, payload);
+        assert_ok!(Multitoken::permit_approval(relayer.clone(), 2, 3, true, 2, signature.clone()));
+        System::assert_last_event(
+            Event::ApprovalForAll {
+                account: 2,
+                operator: 3,
+                approved: true,
+            }
+            .into(),
+        );
+        assert!(Multitoken::is_approved_for_all(&2, &3));
+        assert_eq!(Multitoken::permit_nonce(2), 1);
+        // The nonce moved on, so the same permit cannot be replayed.
+        assert_noop!(
+            Multitoken::permit_approval(relayer.clone(), 2, 3, true, 2, signature),
+            Error::<Test>::InvalidSignature
+        );
+
+        let payload = Multitoken::permit_payload(&2, &3, false, 1, 2);
+        System::set_block_number(3);
+        assert_noop!(
+            Multitoken::permit_approval(
+                relayer.clone(),
+                2,
+                3,
+                false,
+                2,
+                TestSignature(2, payload.clone())
+            ),
+            Error::<Test>::PermitExpired
+        );
+        System::set_block_number(2);
+        assert_ok!(Multitoken::permit_approval(relayer, 2, 3, false, 2, TestSignature(2, payload)));
+        assert!(!Multitoken::is_approved_for_all(&2, &3));
+    });
+}
+
+#[test]
+fn test_operators_of() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let account = RuntimeOrigin::signed(2);
+        assert!(Multitoken::operators_of(&2).is_empty());
+        assert_ok!(Multitoken::set_approval_for_all(account.clone(), 3, true, None));
+        assert_ok!(Multitoken::set_approval_for_all(account.clone(), 4, true, Some(2)));
+        assert_ok!(Multitoken::set_capped_approval(account.clone(), 5, 10));
+        assert_ok!(Multitoken::set_approval_for_all(RuntimeOrigin::signed(6), 7, true, None));
+
+        let mut operators = Multitoken::operators_of(&2);
+        operators.sort();
+        assert_eq!(operators, vec![3, 4, 5]);
+
+        System::set_block_number(3);
+        assert_ok!(Multitoken::set_approval_for_all(account, 5, false, None));
+        assert_eq!(Multitoken::operators_of(&2), vec![3]);
+        assert_eq!(Multitoken::operators_of(&6), vec![7]);
+    });
+}
+
+#[test]
+fn test_sub_delegation() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner, 2, 0, 100));
+        let account = RuntimeOrigin::signed(2);
+        assert_noop!(
+            Multitoken::set_operator_delegable(account.clone(), 3, true),
+            Error::<Test>::InsufficientApprovalForAll
+        );
+        assert_ok!(Multitoken::set_approval_for_all(account.clone(), 3, true, None));
+        assert_ok!(Multitoken::set_approval_for_all(account.clone(), 7, true, None));
+        assert_ok!(Multitoken::set_operator_delegable(account.clone(), 3, true));
+        System::assert_last_event(
+            Event::OperatorDelegableSet {
+                owner: 2,
+                operator: 3,
+                delegable: true,
+            }
+            .into(),
+        );
+        assert_noop!(
+            Multitoken::set_sub_operator(RuntimeOrigin::signed(7), 2, 4, Some(50)),
+            Error::<Test>::NotDelegator
+        );
+
+        assert_ok!(Multitoken::set_sub_operator(RuntimeOrigin::signed(3), 2, 4, Some(50)));
+        System::assert_last_event(
+            Event::SubOperatorSet {
+                owner: 2,
+                delegator: 3,
+                sub_operator: 4,
+                cap: Some(50),
+            }
+            .into(),
+        );
+        assert_noop!(
+            Multitoken::set_sub_operator(RuntimeOrigin::signed(4), 2, 5, Some(60)),
+            Error::<Test>::ApprovalCapExceeded
+        );
+        assert_ok!(Multitoken::set_sub_operator(RuntimeOrigin::signed(4), 2, 5, Some(20)));
+        assert_eq!(Multitoken::sub_operator(2, 5).map(|delegation| delegation.depth), Some(2));
+        assert_noop!(
+            Multitoken::set_sub_operator(RuntimeOrigin::signed(5), 2, 6, Some(10)),
+            Error::<Test>::DelegationTooDeep
+        );
+        assert_noop!(
+            Multitoken::set_sub_operator(RuntimeOrigin::signed(8), 2, 5, None),
+            Error::<Test>::NotDelegator
+        );
+
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(5), 2, 9, 0, 15));
+        assert_eq!(Multitoken::sub_operator(2, 5).map(|delegation| delegation.cap), Some(5));
+        assert_noop!(
+            Multitoken::safe_transfer_from(RuntimeOrigin::signed(5), 2, 9, 0, 6),
+            Error::<Test>::ApprovalCapExceeded
+        );
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &5, &0, 5),
+            AuthorizationResult::GrantedBySubOperator { remaining: 5 }
+        );
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &5, &0, 6),
+            AuthorizationResult::DeniedInsufficientAllowance { remaining: 5 }
+        );
+        // An exhausted sub-operator can still rely on the other approvals of the owner.
+        assert_ok!(Multitoken::approve(account.clone(), 5, 0, 6, None));
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &5, &0, 6),
+            AuthorizationResult::GrantedByAllowance { remaining: 6 }
+        );
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(5), 2, 9, 0, 6));
+        assert_eq!(Multitoken::sub_operator(2, 5).map(|delegation| delegation.cap), Some(5));
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(4), 2, 9, 0, 10));
+
+        // Revoking the delegable operator revokes every sub-operator below it.
+        assert_ok!(Multitoken::set_operator_delegable(account.clone(), 3, false));
+        for sub_operator in [4, 5] {
+            assert_noop!(
+                Multitoken::safe_transfer_from(RuntimeOrigin::signed(sub_operator), 2, 9, 0, 1),
+                Error::<Test>::InsufficientApprovalForAll
+            );
+        }
+        assert_ok!(Multitoken::set_sub_operator(account.clone(), 2, 4, None));
+        assert!(Multitoken::sub_operator(2, 4).is_none());
+        assert_eq!(Multitoken::balance_of(&9, &0), 31);
+
+        // Sub-operators of a capped operator share what is left of its cap.
+        assert_ok!(Multitoken::set_capped_approval(account.clone(), 10, 30));
+        assert_ok!(Multitoken::set_operator_delegable(account, 10, true));
+        assert_noop!(
+            Multitoken::set_sub_operator(RuntimeOrigin::signed(10), 2, 11, Some(31)),
+            Error::<Test>::ApprovalCapExceeded
+        );
+        assert_ok!(Multitoken::set_sub_operator(RuntimeOrigin::signed(10), 2, 11, Some(30)));
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(11), 2, 9, 0, 20));
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &11, &0, 10),
+            AuthorizationResult::GrantedBySubOperator { remaining: 10 }
+        );
+        assert_noop!(
+            Multitoken::safe_transfer_from(RuntimeOrigin::signed(10), 2, 9, 0, 11),
+            Error::<Test>::ApprovalCapExceeded
+        );
+        assert_ok!(Multitoken::safe_transfer_from(RuntimeOrigin::signed(10), 2, 9, 0, 10));
+        assert_noop!(
+            Multitoken::safe_transfer_from(RuntimeOrigin::signed(11), 2, 9, 0, 1),
+            Error::<Test>::ApprovalCapExceeded
+        );
+        assert_eq!(Multitoken::balance_of(&9, &0), 61);
+    });
+}
+
+#[test]
+fn test_approve_transfer_once() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner, 2, 0, 100));
+        let account = RuntimeOrigin::signed(2);
+        assert_noop!(
+            Multitoken::approve_transfer_once(account.clone(), 3, 5, 10),
+            Error::<Test>::CollectionDoesNotExist
+        );
+        assert_ok!(Multitoken::approve_transfer_once(account.clone(), 3, 0, 40));
+        System::assert_last_event(
+            Event::OneShotApproval {
+                owner: 2,
+                operator: 3,
+                id: 0,
+                amount: 40,
+            }
+            .into(),
+        );
+        assert_eq!(
+            Multitoken::explain_authorization(&2, &3, &0, 40),
+            AuthorizationResult::GrantedOnce { amount: 40 }
+        );
+
+        let operator = RuntimeOrigin::signed(3);
+        assert_noop!(
+            Multitoken::safe_transfer_from(operator.clone(), 2, 4, 0, 41),
+            Error::<Test>::AllowanceExceeded
+        );
+        assert_ok!(Multitoken::safe_transfer_from(operator.clone(), 2, 4, 0, 25));
+        assert!(!OneShotApprovals::<Test>::contains_key(2, (0, 3)));
+        // The approval is gone even though it covered more than what was moved.
+        assert_noop!(
+            Multitoken::safe_transfer_from(operator.clone(), 2, 4, 0, 1),
+            Error::<Test>::InsufficientApprovalForAll
+        );
+
+        assert_ok!(Multitoken::approve_transfer_once(account.clone(), 3, 0, 10));
+        assert_ok!(Multitoken::approve_transfer_once(account, 3, 0, 0));
+        assert_noop!(
+            Multitoken::safe_transfer_from(operator, 2, 4, 0, 1),
+            Error::<Test>::InsufficientApprovalForAll
+        );
+        assert_eq!(Multitoken::balance_of(&4, &0), 25);
+    });
+}
+
+#[test]
+fn test_operator_filter() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        let account = RuntimeOrigin::signed(2);
+        assert_ok!(Multitoken::set_approval_for_all(account.clone(), 3, true, None));
+        DeniedOperator::set(Some(3));
+
+        assert_noop!(
+            Multitoken::set_approval_for_all(account.clone(), 3, true, None),
+            Error::<Test>::OperatorNotAllowed
+        );
+        assert_noop!(
+            Multitoken::set_capped_approval(account.clone(), 3, 10),
+            Error::<Test>::OperatorNotAllowed
+        );
+        assert_noop!(
+            Multitoken::set_approval_for_collection(account.clone(), 3, 0, true, None),
+            Error::<Test>::OperatorNotAllowed
+        );
+        assert_noop!(
+            Multitoken::approve(account.clone(), 3, 0, 10, None),
+            Error::<Test>::OperatorNotAllowed
+        );
+        assert_noop!(
+            Multitoken::increase_allowance(account.clone(), 3, 0, 10),
+            Error::<Test>::OperatorNotAllowed
+        );
+        assert_noop!(
+            Multitoken::approve_transfer_once(account.clone(), 3, 0, 10),
+            Error::<Test>::OperatorNotAllowed
+        );
+        assert_ok!(Multitoken::set_approval_for_all(account.clone(), 4, true, None));
+
+        // Existing approvals can still be revoked.
+        assert_ok!(Multitoken::set_approval_for_all(account, 3, false, None));
+        assert!(!Multitoken::is_approved_for_all(&2, &3));
+        DeniedOperator::set(None);
+    });
+}
+
+#[test]
+fn test_burn_from() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner, 2, 0, 100));
+        let operator = RuntimeOrigin::signed(3);
+        assert_noop!(
+            Multitoken::burn_from(operator.clone(), 2, 0, 10),
+            Error::<Test>::InsufficientApprovalForAll
+        );
+
+        assert_ok!(Multitoken::approve(RuntimeOrigin::signed(2), 3, 0, 30, None));
+        assert_ok!(Multitoken::burn_from(operator.clone(), 2, 0, 20));
+        System::assert_last_event(
+            Event::TransferSingle {
+                operator: 3,
+                from: Some(2),
+                to: None,
+                id: 0,
+                value: 20,
+                fungible: true,
+            }
+            .into(),
+        );
+        assert_noop!(Multitoken::burn_from(operator, 2, 0, 20), Error::<Test>::AllowanceExceeded);
+        assert_ok!(Multitoken::burn_from(RuntimeOrigin::signed(2), 2, 0, 30));
+        assert_eq!(Multitoken::balance_of(&2, &0), 50);
+        assert_eq!(Multitoken::total_supply(0), 50);
+        assert_eq!(Multitoken::burned(0), 50);
+    });
+}
+
+#[test]
+fn test_set_approvals() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let account = RuntimeOrigin::signed(2);
+        assert_noop!(Multitoken::set_approvals(account.clone(), vec![]), Error::<Test>::EmptyBatch);
+        assert_noop!(
+            Multitoken::set_approvals(account.clone(), vec![(3, true), (2, true)]),
+            Error::<Test>::InvalidOperator
+        );
+        // One more than `MaxBatchSize`.
+        let too_many: Vec<_> = (10..21).map(|operator| (operator, true)).collect();
+        assert_noop!(
+            Multitoken::set_approvals(account.clone(), too_many),
+            Error::<Test>::BatchTooLarge
+        );
+
+        assert_ok!(Multitoken::set_approval_for_all(account.clone(), 4, true, None));
+        assert_ok!(Multitoken::set_approvals(account, vec![(3, true), (4, false), (5, true)]));
+        assert!(Multitoken::is_approved_for_all(&2, &3));
+        assert!(!Multitoken::is_approved_for_all(&2, &4));
+        assert!(Multitoken::is_approved_for_all(&2, &5));
+        for (operator, approved) in [(3, true), (4, false), (5, true)] {
+            System::assert_has_event(
+                Event::ApprovalForAll {
+                    account: 2,
+                    operator,
+                    approved,
+                }
+                .into(),
+            );
+        }
+    });
+}
+
+#[test]
+fn test_fungibles_approvals() {
+    use frame_support::traits::tokens::fungibles::{approvals, Inspect};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::mint(owner, 2, 0, 100));
+        assert!(<Multitoken as Inspect<u64>>::asset_exists(0));
+        assert_eq!(<Multitoken as Inspect<u64>>::total_issuance(0), 100);
+        assert_eq!(<Multitoken as Inspect<u64>>::balance(0, &2), 100);
+
+        assert_noop!(
+            <Multitoken as approvals::Mutate<u64>>::approve(5, &2, &3, 10),
+            Error::<Test>::CollectionDoesNotExist
+        );
+        assert_ok!(<Multitoken as approvals::Mutate<u64>>::approve(0, &2, &3, 30));
+        assert_eq!(Allowances::<Test>::get(2, (0, 3)), Some((30, None)));
+        assert_eq!(<Multitoken as approvals::Inspect<u64>>::allowance(0, &2, &3), 30);
+        assert_eq!(<Multitoken as approvals::Inspect<u64>>::allowance(0, &2, &4), 0);
+
+        assert_ok!(<Multitoken as approvals::Mutate<u64>>::transfer_from(0, &2, &3, &4, 20));
+        assert_eq!(Multitoken::balance_of(&4, &0), 20);
+        assert_eq!(<Multitoken as approvals::Inspect<u64>>::allowance(0, &2, &3), 10);
+        assert_noop!(
+            <Multitoken as approvals::Mutate<u64>>::transfer_from(0, &2, &3, &4, 11),
+            Error::<Test>::AllowanceExceeded
+        );
+        assert_noop!(
+            <Multitoken as approvals::Mutate<u64>>::transfer_from(0, &2, &5, &4, 1),
+            Error::<Test>::InsufficientApprovalForAll
+        );
+
+        // A transfer that fails after the allowance was checked leaves the allowance intact.
+        assert_ok!(Multitoken::freeze_account(RuntimeOrigin::signed(1), 0, 2, true));
+        assert_noop!(
+            <Multitoken as approvals::Mutate<u64>>::transfer_from(0, &2, &3, &4, 5),
+            Error::<Test>::Frozen
+        );
+        assert_eq!(<Multitoken as approvals::Inspect<u64>>::allowance(0, &2, &3), 10);
+    });
+}
+
+#[test]
+fn test_on_idle_prunes_expired_approvals() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let account = RuntimeOrigin::signed(2);
+        assert_ok!(Multitoken::set_approval_for_all(account.clone(), 3, true, Some(5)));
+        assert_ok!(Multitoken::set_approval_for_all(account.clone(), 4, true, Some(20)));
+        assert_ok!(Multitoken::set_approval_for_all(account.clone(), 5, true, None));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert_ok!(Multitoken::set_approval_for_collection(account.clone(), 3, 0, true, Some(5)));
+        assert_ok!(Multitoken::set_approval_for_collection(account.clone(), 4, 0, true, None));
+        assert_ok!(Multitoken::approve(account.clone(), 3, 0, 10, Some(5)));
+        assert_ok!(Multitoken::approve(account, 4, 0, 10, Some(20)));
+
+        // Nothing has expired yet.
+        Multitoken::on_idle(1, Weight::MAX);
+        assert_eq!(OperatorApprovals::<Test>::iter_prefix(2).count(), 3);
+        assert_eq!(CollectionApprovals::<Test>::iter_prefix(2).count(), 2);
+        assert_eq!(Allowances::<Test>::iter_prefix(2).count(), 2);
+
+        System::set_block_number(10);
+        Multitoken::on_idle(10, Weight::MAX);
+        System::assert_has_event(
+            Event::ApprovalExpired {
+                account: 2,
+                operator: 3,
+            }
+            .into(),
+        );
+        assert!(!OperatorApprovals::<Test>::contains_key(2, 3));
+        assert!(!ApprovalsByOperator::<Test>::contains_key(3, 2));
+        assert!(OperatorApprovals::<Test>::contains_key(2, 4));
+        assert!(OperatorApprovals::<Test>::contains_key(2, 5));
+        // Expired approvals for single collections and allowances are revoked too.
+        System::assert_has_event(
+            Event::CollectionApproval {
+                account: 2,
+                operator: 3,
+                id: 0,
+                approved: false,
+            }
+            .into(),
+        );
+        System::assert_has_event(
+            Event::Approval {
+                owner: 2,
+                operator: 3,
+                id: 0,
+                amount: 0,
+            }
+            .into(),
+        );
+        assert_eq!(CollectionApprovals::<Test>::get(2, (0, 3)), None);
+        assert_eq!(CollectionApprovals::<Test>::get(2, (0, 4)), Some(None));
+        assert_eq!(Allowances::<Test>::get(2, (0, 3)), None);
+        assert_eq!(Allowances::<Test>::get(2, (0, 4)), Some((10, Some(20))));
+        assert!(ApprovalExpirySweepCursor::<Test>::get().is_none());
+    });
+}
+
+#[test]
+fn test_expired_approvals_pruning_resumes_in_later_blocks() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let account = RuntimeOrigin::signed(2);
+        assert_ok!(Multitoken::set_approval_for_all(account.clone(), 3, true, Some(5)));
+        assert_ok!(Multitoken::set_approval_for_all(account.clone(), 4, true, Some(5)));
+        assert_ok!(Multitoken::create(RuntimeOrigin::signed(1), true));
+        assert_ok!(Multitoken::approve(account, 3, 0, 10, Some(5)));
+
+        let db_weight = <Test as frame_system::Config>::DbWeight::get();
+        // The sweeps before it read four items, then a single entry is visited per call.
+        let budget = db_weight
+            .reads(4)
+            .saturating_add(db_weight.reads_writes(2, 1))
+            .saturating_add(db_weight.reads_writes(1, 4));
+        System::set_block_number(10);
+        Multitoken::on_idle(10, budget);
+        assert_eq!(OperatorApprovals::<Test>::iter_prefix(2).count(), 1);
+        assert_eq!(ApprovalExpirySweepCursor::<Test>::get().map(|(map, _)| map), Some(0));
+
+        // Each call picks up where the previous one stopped, moving on to the next maps.
+        while Allowances::<Test>::contains_key(2, (0, 3)) {
+            Multitoken::on_idle(10, budget);
+            assert_ne!(ApprovalExpirySweepCursor::<Test>::get(), None);
+        }
+        assert_eq!(OperatorApprovals::<Test>::iter_prefix(2).count(), 0);
+        assert_eq!(ApprovalExpirySweepCursor::<Test>::get().map(|(map, _)| map), Some(2));
+    });
+}
+
+#[test]
+fn test_two_step_collection_ownership_transfer() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_noop!(
+            Multitoken::transfer_collection_ownership(RuntimeOrigin::signed(2), 0, 2),
+            Error::<Test>::InvalidOwner
+        );
+        assert_noop!(
+            Multitoken::accept_collection_ownership(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::NotPendingOwner
+        );
+
+        assert_ok!(Multitoken::transfer_collection_ownership(owner.clone(), 0, 3));
+        assert_ok!(Multitoken::transfer_collection_ownership(owner.clone(), 0, 2));
+        System::assert_last_event(
+            Event::OwnershipTransferProposed {
+                id: 0,
+                new_owner: 2,
+            }
+            .into(),
+        );
+        // Only the latest offer can be accepted, and the collection stays put until then.
+        assert_noop!(
+            Multitoken::accept_collection_ownership(RuntimeOrigin::signed(3), 0),
+            Error::<Test>::NotPendingOwner
+        );
+        assert_eq!(Multitoken::collection_owner(&0), Some(1));
+
+        assert_ok!(Multitoken::accept_collection_ownership(RuntimeOrigin::signed(2), 0));
+        System::assert_last_event(Event::OwnerChanged { id: 0, owner: 2 }.into());
+        assert_eq!(Multitoken::collection_owner(&0), Some(2));
+        assert_eq!(Multitoken::pending_owner(0), None);
+        assert_eq!(OwnerCollectionCount::<Test>::get(1), 0);
+        assert_eq!(OwnerCollectionCount::<Test>::get(2), 1);
+        assert_noop!(Multitoken::mint(owner, 1, 0, 10), Error::<Test>::InvalidOwner);
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(2), 1, 0, 10));
+    });
+}
+
+#[test]
+fn test_accepting_ownership_dismisses_the_team() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::set_team(owner.clone(), 0, 3, 3, 3));
+        assert_ok!(Multitoken::transfer_collection_ownership(owner.clone(), 0, 2));
+
+        assert_ok!(Multitoken::accept_collection_ownership(RuntimeOrigin::signed(2), 0));
+        System::assert_has_event(
+            Event::TeamChanged {
+                id: 0,
+                issuer: 2,
+                admin: 2,
+                freezer: 2,
+            }
+            .into(),
+        );
+        assert_eq!(Multitoken::collection_team(0), None);
+        // The roles of the previous team are gone along with it.
+        assert_noop!(
+            Multitoken::mint(RuntimeOrigin::signed(3), 3, 0, 10),
+            Error::<Test>::InvalidOwner
+        );
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(2), 3, 0, 10));
+
+        // Collections being destroyed cannot change hands.
+        assert_ok!(Multitoken::transfer_collection_ownership(owner.clone(), 1, 2));
+        assert_ok!(Multitoken::start_destroy(owner, 1));
+        assert_noop!(
+            Multitoken::accept_collection_ownership(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::Destroying
+        );
+        assert_eq!(Multitoken::collection_owner(&1), Some(1));
+    });
+}
+
+#[test]
+fn test_collection_team_roles() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_noop!(
+            Multitoken::set_team(RuntimeOrigin::signed(2), 0, 2, 3, 4),
+            Error::<Test>::InvalidOwner
+        );
+        assert_ok!(Multitoken::set_team(owner.clone(), 0, 2, 3, 4));
+        System::assert_last_event(
+            Event::TeamChanged {
+                id: 0,
+                issuer: 2,
+                admin: 3,
+                freezer: 4,
+            }
+            .into(),
+        );
+
+        // Each role can only be used by its holder, not even by the owner.
+        assert_noop!(Multitoken::mint(owner.clone(), 5, 0, 10), Error::<Test>::InvalidOwner);
+        assert_ok!(Multitoken::mint(RuntimeOrigin::signed(2), 5, 0, 10));
+        let payload = Multitoken::claim_payload(&0, &6, 5, 0);
+        let by_owner = TestSignature(1, payload.clone());
+        assert_noop!(
+            Multitoken::claim_mint(RuntimeOrigin::signed(6), 0, 5, by_owner),
+            Error::<Test>::InvalidSignature
+        );
+        let by_issuer = TestSignature(2, payload);
+        assert_ok!(Multitoken::claim_mint(RuntimeOrigin::signed(6), 0, 5, by_issuer));
+        assert_eq!(Multitoken::balance_of(&6, &0), 5);
+        assert_noop!(
+            Multitoken::set_uri(RuntimeOrigin::signed(2), 0, b"ipfs://team".to_vec()),
+            Error::<Test>::InvalidOwner
+        );
+        assert_ok!(Multitoken::set_uri(RuntimeOrigin::signed(3), 0, b"ipfs://team".to_vec()));
+        assert_noop!(
+            Multitoken::pause_collection(RuntimeOrigin::signed(3), 0),
+            Error::<Test>::InvalidOwner
+        );
+        assert_ok!(Multitoken::pause_collection(RuntimeOrigin::signed(4), 0));
+
+        // The owner keeps the rights outside the team and can take the roles back.
+        assert_noop!(
+            Multitoken::set_team(RuntimeOrigin::signed(2), 0, 2, 2, 2),
+            Error::<Test>::InvalidOwner
+        );
+        assert_ok!(Multitoken::set_team(owner.clone(), 0, 1, 1, 1));
+        assert_eq!(Multitoken::collection_team(0), None);
+        assert_ok!(Multitoken::unpause_collection(owner.clone(), 0));
+        assert_ok!(Multitoken::mint(owner, 5, 0, 10));
+    });
+}
+
+#[test]
+fn test_multi_stage_destroy() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let owner = RuntimeOrigin::signed(1);
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        assert_ok!(Multitoken::create(owner.clone(), true));
+        for holder in 2..5 {
+            assert_ok!(Multitoken::mint(owner.clone(), holder, 0, 10));
+        }
+        assert_ok!(Multitoken::approve(RuntimeOrigin::signed(2), 5, 0, 5, None));
+        assert_ok!(Multitoken::approve(RuntimeOrigin::signed(2), 5, 1, 5, None));
+        assert_ok!(Multitoken::freeze_account(owner.clone(), 0, 6, true));
+        assert_ok!(Multitoken::allow_claim(owner.clone(), 0, 6, 5));
+        let witness = DestroyWitness {
+            accounts: 2,
+            approvals: 10,
+        };
+        assert_noop!(
+            Multitoken::destroy_accounts(RuntimeOrigin::signed(6), 0, witness),
+            Error::<Test>::NotDestroying
+        );
+        assert_noop!(
+            Multitoken::start_destroy(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::InvalidOwner
+        );
+
+        assert_ok!(Multitoken::start_destroy(owner.clone(), 0));
+        System::assert_last_event(Event::DestructionStarted { id: 0 }.into());
+        assert_noop!(Multitoken::start_destroy(owner, 0), Error::<Test>::Destroying);
+        assert_noop!(
+            Multitoken::safe_transfer_from(RuntimeOrigin::signed(2), 2, 6, 0, 1),
+            Error::<Test>::Destroying
+        );
+        assert_noop!(
+            Multitoken::force_set_balance(RuntimeOrigin::root(), 0, 6, 1),
+            Error::<Test>::Destroying
+        );
+        assert_noop!(
+            Multitoken::merge_collections(RuntimeOrigin::signed(1), 0, 1, 10),
+            Error::<Test>::Destroying
+        );
+        // Approvals granted now would outlive the collection and apply to a recreated one.
+        assert_noop!(
+            Multitoken::approve(RuntimeOrigin::signed(3), 5, 0, 5, None),
+            Error::<Test>::Destroying
+        );
+        assert_noop!(
+            Multitoken::increase_allowance(RuntimeOrigin::signed(3), 5, 0, 5),
+            Error::<Test>::Destroying
+        );
+        assert_noop!(
+            Multitoken::set_approval_for_collection(RuntimeOrigin::signed(3), 5, 0, true, None),
+            Error::<Test>::Destroying
+        );
+        assert_noop!(
+            Multitoken::approve_transfer_once(RuntimeOrigin::signed(3), 5, 0, 5),
+            Error::<Test>::Destroying
+        );
+        assert_noop!(
+            Multitoken::freeze_account(RuntimeOrigin::signed(1), 0, 7, true),
+            Error::<Test>::Destroying
+        );
+
+        // Anyone can push the destruction forward, within the bounds of the witness.
+        assert_ok!(Multitoken::destroy_accounts(RuntimeOrigin::signed(6), 0, witness));
+        System::assert_last_event(
+            Event::AccountsDestroyed {
+                id: 0,
+                accounts_destroyed: 2,
+            }
+            .into(),
+        );
+        assert_eq!(Multitoken::total_supply(0), 10);
+        assert_noop!(
+            Multitoken::finish_destroy(RuntimeOrigin::signed(6), 0),
+            Error::<Test>::DestroyIncomplete
+        );
+
+        // The remaining holder and the account freeze fill the witness, the claim is left.
+        assert_ok!(Multitoken::destroy_accounts(RuntimeOrigin::signed(6), 0, witness));
+        System::assert_last_event(
+            Event::AccountsDestroyed {
+                id: 0,
+                accounts_destroyed: 1,
+            }
+            .into(),
+        );
+        assert!(!FrozenAccounts::<Test>::get(0, 6));
+        assert_eq!(Claimable::<Test>::get(0, 6), 5);
+        assert_eq!(CollectionDestroying::<Test>::get(0), Some(DestroyStage::Accounts));
+
+        assert_ok!(Multitoken::destroy_accounts(RuntimeOrigin::signed(6), 0, witness));
+        System::assert_last_event(
+            Event::ApprovalsDestroyed {
+                id: 0,
+                approvals_destroyed: 1,
+            }
+            .into(),
+        );
+        assert_eq!(Multitoken::total_supply(0), 0);
+        assert_eq!(Claimable::<Test>::get(0, 6), 0);
+        assert_eq!(Allowances::<Test>::get(2, (0, 5)), None);
+        assert_eq!(Allowances::<Test>::get(2, (1, 5)), Some((5, None)));
+        assert_eq!(CollectionDestroying::<Test>::get(0), Some(DestroyStage::Done));
+
+        assert_ok!(Multitoken::finish_destroy(RuntimeOrigin::signed(6), 0));
+        System::assert_last_event(Event::CollectionDestroyed { id: 0 }.into());
+        assert!(CollectionsV2::<Test>::get(0).is_none());
+        assert!(CollectionDestroying::<Test>::get(0).is_none());
+        assert_eq!(OwnerCollectionCount::<Test>::get(1), 1);
+    });
+}
+
+#[test]
+fn test_mint_batch_accepts_signed_origins() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        // Signed callers get past the origin check and reach the ownership check.
+        assert_noop!(
+            Multitoken::mint_batch(RuntimeOrigin::signed(1), 2, vec![0], vec![10]),
+            Error::<Test>::CollectionDoesNotExist
+        );
+        assert_noop!(
+            Multitoken::mint_batch(RuntimeOrigin::root(), 2, vec![0], vec![10]),
+            sp_runtime::DispatchError::BadOrigin
+        );
+    });
+}
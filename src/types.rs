@@ -0,0 +1,161 @@
+//! Types used by the multitoken pallet.
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// Approval granted by an account to an operator.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ApprovalRecord<BlockNumber> {
+    /// Whether the operator can transfer every token of the account.
+    pub global: bool,
+    /// Last block in which the approval is valid, if it ever expires.
+    pub expires: Option<BlockNumber>,
+}
+
+impl<BlockNumber: PartialOrd> ApprovalRecord<BlockNumber> {
+    /// Returns true if the record grants a global approval that is still valid at block `now`.
+    pub fn is_active(&self, now: BlockNumber) -> bool {
+        self.global && is_unexpired(&self.expires, &now)
+    }
+}
+
+/// Returns true if an approval valid until block `expires`, if ever, is still valid at block
+/// `now`.
+pub fn is_unexpired<BlockNumber: PartialOrd>(
+    expires: &Option<BlockNumber>,
+    now: &BlockNumber,
+) -> bool {
+    match expires {
+        Some(expires) => now <= expires,
+        None => true,
+    }
+}
+
+/// Everything the pallet stores about a collection, so that it can be loaded with a single read.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CollectionDetails<AccountId, Amount> {
+    /// Account that created the collection.
+    pub owner: AccountId,
+    /// Amount of tokens in circulation.
+    pub total_supply: Amount,
+    /// Maximum amount of tokens that can ever be in circulation, if capped.
+    pub max_supply: Option<Amount>,
+    /// Whether the collection is frozen.
+    pub frozen: bool,
+    /// Whether the collection is fungible. Non-fungible collections have a supply of at most one.
+    pub fungible: bool,
+}
+
+/// Human readable metadata of a collection.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CollectionMetadata<BoundedString> {
+    /// Name of the collection.
+    pub name: BoundedString,
+    /// Ticker symbol of the collection.
+    pub symbol: BoundedString,
+}
+
+/// What to do with a collection URI longer than `MaxUriLength`.
+#[derive(Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum UriPolicy {
+    /// Reject the URI with `UriTooLong`.
+    #[default]
+    Reject,
+    /// Store the first `MaxUriLength` bytes of the URI.
+    Truncate,
+}
+
+/// Share of every transfer of a collection paid to its owner and to the treasury.
+#[derive(
+    Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct TransferTax {
+    /// Basis points of each transfer credited to the owner of the collection.
+    pub owner_bps: u16,
+    /// Basis points of each transfer credited to the treasury.
+    pub treasury_bps: u16,
+}
+
+/// Royalty owed on the sales of a collection, as in ERC2981.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RoyaltyInfo<AccountId> {
+    /// Account receiving the royalties.
+    pub recipient: AccountId,
+    /// Basis points of each sale price owed to `recipient`.
+    pub bps: u16,
+}
+
+/// Rights delegated to a sub-operator by an operator of the owner of the tokens.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SubDelegation<AccountId, Amount> {
+    /// Operator or sub-operator that registered the sub-operator.
+    pub delegator: AccountId,
+    /// Amount of tokens that the sub-operator can still move.
+    pub cap: Amount,
+    /// Number of delegations between the owner's delegable operator and the sub-operator,
+    /// starting at one.
+    pub depth: u32,
+}
+
+/// Operations that run across several calls, reported by `BatchProgress`.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum BatchOp {
+    /// `force_clear_balances`.
+    ClearBalances,
+    /// `merge_collections`.
+    MergeCollections,
+}
+
+/// Accounts that manage a collection on behalf of its owner.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CollectionTeam<AccountId> {
+    /// Account that can mint tokens of the collection.
+    pub issuer: AccountId,
+    /// Account that can manage the metadata of the collection.
+    pub admin: AccountId,
+    /// Account that can freeze and pause the collection and its holders.
+    pub freezer: AccountId,
+}
+
+/// Role in the team of a collection.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum CollectionRole {
+    /// Mints tokens.
+    Issuer,
+    /// Manages metadata.
+    Admin,
+    /// Freezes and pauses.
+    Freezer,
+}
+
+/// Upper bounds on the work done by a single `destroy_accounts` call.
+#[derive(
+    Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct DestroyWitness {
+    /// Maximum number of per-account entries removed: balances, account freezes, allowlisted
+    /// recipients and claims.
+    #[codec(compact)]
+    pub accounts: u32,
+    /// Maximum number of approval entries visited while looking for the approvals of the
+    /// collection.
+    #[codec(compact)]
+    pub approvals: u32,
+}
+
+/// Progress of the destruction of a collection, started by `start_destroy`.
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum DestroyStage {
+    /// The balances of the holders are being removed, followed by the account freezes,
+    /// allowlisted recipients and claims of the collection.
+    #[default]
+    Accounts,
+    /// The approvals for the collection are being removed from `CollectionApprovals`,
+    /// `Allowances` and `OneShotApprovals`, in that order. `map` is the position of the map being
+    /// walked and `cursor` the raw key of the last entry visited in it.
+    Approvals { map: u8, cursor: Option<Vec<u8>> },
+    /// Nothing is left but the collection itself, which `finish_destroy` removes.
+    Done,
+}
@@ -0,0 +1,936 @@
+//! Weights for pallet_multitoken.
+//!
+//! These are conservative estimates derived from the storage accesses of each call. They must be
+//! regenerated on reference hardware from the benchmarks in `benchmarking.rs`:
+//!
+//! ./target/release/node benchmark pallet --chain=dev --steps=50 --repeat=20
+//!     --pallet=pallet_multitoken --extrinsic=* --output=./src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_multitoken.
+pub trait WeightInfo {
+	fn set_approval_for_all() -> Weight;
+	fn safe_transfer_from() -> Weight;
+	fn safe_batch_transfer_from(n: u32) -> Weight;
+	fn mint() -> Weight;
+	fn mint_batch(n: u32) -> Weight;
+	fn burn() -> Weight;
+	fn burn_batch(n: u32) -> Weight;
+	fn create() -> Weight;
+	fn merge_collections(n: u32) -> Weight;
+	fn emit_metadata_update() -> Weight;
+	fn allow_claim() -> Weight;
+	fn claim_and_approve() -> Weight;
+	fn mint_and_distribute(n: u32) -> Weight;
+	fn allow_recipient() -> Weight;
+	fn set_recipient_allowlist_enforced() -> Weight;
+	fn claim_mint() -> Weight;
+	fn transfer() -> Weight;
+	fn set_collection_hook() -> Weight;
+	fn burn_batch_from(n: u32) -> Weight;
+	fn force_set_balance() -> Weight;
+	fn destroy_collection() -> Weight;
+	fn set_paused() -> Weight;
+	fn freeze_collection() -> Weight;
+	fn freeze_account() -> Weight;
+	fn set_uri() -> Weight;
+	fn mint_batch_with_uris(n: u32) -> Weight;
+	fn set_metadata() -> Weight;
+	fn set_scale() -> Weight;
+	fn bridge_mint() -> Weight;
+	fn set_capped_approval() -> Weight;
+	fn mint_idempotent() -> Weight;
+	fn ensure_collection_and_mint() -> Weight;
+	fn distribute(n: u32) -> Weight;
+	fn force_clear_balances(n: u32) -> Weight;
+	fn set_transfer_tax() -> Weight;
+	fn create_deterministic() -> Weight;
+	fn set_royalty() -> Weight;
+	fn set_royalty_recipient() -> Weight;
+	fn execute_sale() -> Weight;
+	fn set_max_supply() -> Weight;
+	fn set_inflation_schedule() -> Weight;
+	fn process_inflation(n: u32) -> Weight;
+	fn safe_transfer_from_with_memo() -> Weight;
+	fn pause_collection() -> Weight;
+	fn unpause_collection() -> Weight;
+	fn set_orphan_fallback() -> Weight;
+	fn set_approval_for_collection() -> Weight;
+	fn approve() -> Weight;
+	fn clear_all_approvals(n: u32) -> Weight;
+	fn increase_allowance() -> Weight;
+	fn decrease_allowance() -> Weight;
+	fn permit_approval() -> Weight;
+	fn set_operator_delegable() -> Weight;
+	fn set_sub_operator() -> Weight;
+	fn approve_transfer_once() -> Weight;
+	fn burn_from() -> Weight;
+	fn set_approvals(n: u32) -> Weight;
+	fn transfer_collection_ownership() -> Weight;
+	fn accept_collection_ownership() -> Weight;
+	fn set_team() -> Weight;
+	fn start_destroy() -> Weight;
+	fn destroy_accounts(a: u32, p: u32) -> Weight;
+	fn finish_destroy() -> Weight;
+	fn set_mint_consent() -> Weight;
+}
+
+/// Weights for pallet_multitoken using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn set_approval_for_all() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+
+	fn safe_transfer_from() -> Weight {
+		Weight::from_parts(58_000_000, 6_208)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+
+	fn safe_batch_transfer_from(n: u32) -> Weight {
+		Weight::from_parts(18_000_000, 3_593)
+			.saturating_add(Weight::from_parts(39_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((11_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_615).saturating_mul(n.into()))
+	}
+
+	fn mint() -> Weight {
+		Weight::from_parts(33_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+
+	fn mint_batch(n: u32) -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(Weight::from_parts(22_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_615).saturating_mul(n.into()))
+	}
+
+	fn burn() -> Weight {
+		Weight::from_parts(27_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+
+	fn burn_batch(n: u32) -> Weight {
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(Weight::from_parts(17_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_615).saturating_mul(n.into()))
+	}
+
+	fn create() -> Weight {
+		Weight::from_parts(21_000_000, 1_493)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+
+	fn merge_collections(n: u32) -> Weight {
+		Weight::from_parts(29_000_000, 6_158)
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5_230).saturating_mul(n.into()))
+	}
+
+	fn emit_metadata_update() -> Weight {
+		Weight::from_parts(15_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+
+	fn allow_claim() -> Weight {
+		Weight::from_parts(17_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn claim_and_approve() -> Weight {
+		Weight::from_parts(41_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+
+	fn mint_and_distribute(n: u32) -> Weight {
+		Weight::from_parts(24_000_000, 3_593)
+			.saturating_add(Weight::from_parts(22_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_615).saturating_mul(n.into()))
+	}
+
+	fn allow_recipient() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn set_recipient_allowlist_enforced() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn claim_mint() -> Weight {
+		Weight::from_parts(50_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+
+	fn transfer() -> Weight {
+		Weight::from_parts(54_000_000, 6_208)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+
+	fn set_collection_hook() -> Weight {
+		Weight::from_parts(16_000_000, 3_514)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn burn_batch_from(n: u32) -> Weight {
+		Weight::from_parts(15_000_000, 3_593)
+			.saturating_add(Weight::from_parts(17_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_615).saturating_mul(n.into()))
+	}
+
+	fn force_set_balance() -> Weight {
+		Weight::from_parts(23_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+
+	fn destroy_collection() -> Weight {
+		Weight::from_parts(18_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn set_paused() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn freeze_collection() -> Weight {
+		Weight::from_parts(17_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn freeze_account() -> Weight {
+		Weight::from_parts(17_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn set_uri() -> Weight {
+		Weight::from_parts(18_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn mint_batch_with_uris(n: u32) -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(Weight::from_parts(30_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 3_593).saturating_mul(n.into()))
+	}
+
+	fn set_metadata() -> Weight {
+		Weight::from_parts(19_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn set_scale() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn bridge_mint() -> Weight {
+		Weight::from_parts(30_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+
+	fn set_capped_approval() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+
+	fn mint_idempotent() -> Weight {
+		Weight::from_parts(37_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+
+	fn ensure_collection_and_mint() -> Weight {
+		Weight::from_parts(48_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+	}
+
+	fn distribute(n: u32) -> Weight {
+		Weight::from_parts(12_000_000, 3_593)
+			.saturating_add(Weight::from_parts(50_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((11_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((8_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_615).saturating_mul(n.into()))
+	}
+
+	fn force_clear_balances(n: u32) -> Weight {
+		Weight::from_parts(18_000_000, 3_593)
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+
+	fn set_transfer_tax() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn create_deterministic() -> Weight {
+		Weight::from_parts(24_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+
+	fn set_royalty() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn set_royalty_recipient() -> Weight {
+		Weight::from_parts(18_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn execute_sale() -> Weight {
+		Weight::from_parts(95_000_000, 6_208)
+			.saturating_add(T::DbWeight::get().reads(26_u64))
+			.saturating_add(T::DbWeight::get().writes(15_u64))
+	}
+
+	fn set_max_supply() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn set_inflation_schedule() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn process_inflation(n: u32) -> Weight {
+		Weight::from_parts(4_000_000, 1_489)
+			.saturating_add(Weight::from_parts(28_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 3_593).saturating_mul(n.into()))
+	}
+
+	fn safe_transfer_from_with_memo() -> Weight {
+		Weight::from_parts(61_000_000, 6_208)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+
+	fn pause_collection() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn unpause_collection() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn set_orphan_fallback() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn set_approval_for_collection() -> Weight {
+		Weight::from_parts(17_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn approve() -> Weight {
+		Weight::from_parts(17_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn clear_all_approvals(n: u32) -> Weight {
+		Weight::from_parts(12_000_000, 990)
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+	}
+
+	fn increase_allowance() -> Weight {
+		Weight::from_parts(19_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn decrease_allowance() -> Weight {
+		Weight::from_parts(17_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn permit_approval() -> Weight {
+		Weight::from_parts(50_000_000, 3_509)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+
+	fn set_operator_delegable() -> Weight {
+		Weight::from_parts(17_000_000, 3_509)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn set_sub_operator() -> Weight {
+		Weight::from_parts(33_000_000, 3_625)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn approve_transfer_once() -> Weight {
+		Weight::from_parts(17_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn burn_from() -> Weight {
+		Weight::from_parts(30_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+
+	fn set_approvals(n: u32) -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(Weight::from_parts(12_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+
+	fn transfer_collection_ownership() -> Weight {
+		Weight::from_parts(15_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn accept_collection_ownership() -> Weight {
+		Weight::from_parts(24_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+
+	fn set_team() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn start_destroy() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+
+	fn destroy_accounts(a: u32, p: u32) -> Weight {
+		Weight::from_parts(22_000_000, 3_593)
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(a.into()))
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(a.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(a.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 2_615).saturating_mul(p.into()))
+	}
+
+	fn finish_destroy() -> Weight {
+		Weight::from_parts(40_000_000, 3_593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(22_u64))
+	}
+
+	fn set_mint_consent() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn set_approval_for_all() -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+
+	fn safe_transfer_from() -> Weight {
+		Weight::from_parts(58_000_000, 6_208)
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+
+	fn safe_batch_transfer_from(n: u32) -> Weight {
+		Weight::from_parts(18_000_000, 3_593)
+			.saturating_add(Weight::from_parts(39_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((11_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_615).saturating_mul(n.into()))
+	}
+
+	fn mint() -> Weight {
+		Weight::from_parts(33_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+
+	fn mint_batch(n: u32) -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(Weight::from_parts(22_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_615).saturating_mul(n.into()))
+	}
+
+	fn burn() -> Weight {
+		Weight::from_parts(27_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+
+	fn burn_batch(n: u32) -> Weight {
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(Weight::from_parts(17_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_615).saturating_mul(n.into()))
+	}
+
+	fn create() -> Weight {
+		Weight::from_parts(21_000_000, 1_493)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+
+	fn merge_collections(n: u32) -> Weight {
+		Weight::from_parts(29_000_000, 6_158)
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5_230).saturating_mul(n.into()))
+	}
+
+	fn emit_metadata_update() -> Weight {
+		Weight::from_parts(15_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+
+	fn allow_claim() -> Weight {
+		Weight::from_parts(17_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn claim_and_approve() -> Weight {
+		Weight::from_parts(41_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+
+	fn mint_and_distribute(n: u32) -> Weight {
+		Weight::from_parts(24_000_000, 3_593)
+			.saturating_add(Weight::from_parts(22_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_615).saturating_mul(n.into()))
+	}
+
+	fn allow_recipient() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn set_recipient_allowlist_enforced() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn claim_mint() -> Weight {
+		Weight::from_parts(50_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+
+	fn transfer() -> Weight {
+		Weight::from_parts(54_000_000, 6_208)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+
+	fn set_collection_hook() -> Weight {
+		Weight::from_parts(16_000_000, 3_514)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn burn_batch_from(n: u32) -> Weight {
+		Weight::from_parts(15_000_000, 3_593)
+			.saturating_add(Weight::from_parts(17_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_615).saturating_mul(n.into()))
+	}
+
+	fn force_set_balance() -> Weight {
+		Weight::from_parts(23_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+
+	fn destroy_collection() -> Weight {
+		Weight::from_parts(18_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn set_paused() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn freeze_collection() -> Weight {
+		Weight::from_parts(17_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn freeze_account() -> Weight {
+		Weight::from_parts(17_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn set_uri() -> Weight {
+		Weight::from_parts(18_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn mint_batch_with_uris(n: u32) -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(Weight::from_parts(30_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 3_593).saturating_mul(n.into()))
+	}
+
+	fn set_metadata() -> Weight {
+		Weight::from_parts(19_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn set_scale() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn bridge_mint() -> Weight {
+		Weight::from_parts(30_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+
+	fn set_capped_approval() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+
+	fn mint_idempotent() -> Weight {
+		Weight::from_parts(37_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+
+	fn ensure_collection_and_mint() -> Weight {
+		Weight::from_parts(48_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+	}
+
+	fn distribute(n: u32) -> Weight {
+		Weight::from_parts(12_000_000, 3_593)
+			.saturating_add(Weight::from_parts(50_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((11_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((8_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_615).saturating_mul(n.into()))
+	}
+
+	fn force_clear_balances(n: u32) -> Weight {
+		Weight::from_parts(18_000_000, 3_593)
+			.saturating_add(Weight::from_parts(1_200_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+
+	fn set_transfer_tax() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn create_deterministic() -> Weight {
+		Weight::from_parts(24_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+
+	fn set_royalty() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn set_royalty_recipient() -> Weight {
+		Weight::from_parts(18_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn execute_sale() -> Weight {
+		Weight::from_parts(95_000_000, 6_208)
+			.saturating_add(RocksDbWeight::get().reads(26_u64))
+			.saturating_add(RocksDbWeight::get().writes(15_u64))
+	}
+
+	fn set_max_supply() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn set_inflation_schedule() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn process_inflation(n: u32) -> Weight {
+		Weight::from_parts(4_000_000, 1_489)
+			.saturating_add(Weight::from_parts(28_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 3_593).saturating_mul(n.into()))
+	}
+
+	fn safe_transfer_from_with_memo() -> Weight {
+		Weight::from_parts(61_000_000, 6_208)
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+
+	fn pause_collection() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn unpause_collection() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn set_orphan_fallback() -> Weight {
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn set_approval_for_collection() -> Weight {
+		Weight::from_parts(17_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn approve() -> Weight {
+		Weight::from_parts(17_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn clear_all_approvals(n: u32) -> Weight {
+		Weight::from_parts(12_000_000, 990)
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(n.into()))
+	}
+
+	fn increase_allowance() -> Weight {
+		Weight::from_parts(19_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn decrease_allowance() -> Weight {
+		Weight::from_parts(17_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn permit_approval() -> Weight {
+		Weight::from_parts(50_000_000, 3_509)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+
+	fn set_operator_delegable() -> Weight {
+		Weight::from_parts(17_000_000, 3_509)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn set_sub_operator() -> Weight {
+		Weight::from_parts(33_000_000, 3_625)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn approve_transfer_once() -> Weight {
+		Weight::from_parts(17_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn burn_from() -> Weight {
+		Weight::from_parts(30_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+
+	fn set_approvals(n: u32) -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(Weight::from_parts(12_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+
+	fn transfer_collection_ownership() -> Weight {
+		Weight::from_parts(15_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn accept_collection_ownership() -> Weight {
+		Weight::from_parts(24_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+
+	fn set_team() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn start_destroy() -> Weight {
+		Weight::from_parts(16_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+
+	fn destroy_accounts(a: u32, p: u32) -> Weight {
+		Weight::from_parts(22_000_000, 3_593)
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(a.into()))
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(a.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(a.into())))
+			.saturating_add(Weight::from_parts(0, 2_603).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 2_615).saturating_mul(p.into()))
+	}
+
+	fn finish_destroy() -> Weight {
+		Weight::from_parts(40_000_000, 3_593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(22_u64))
+	}
+
+	fn set_mint_consent() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}